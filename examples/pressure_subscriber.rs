@@ -0,0 +1,58 @@
+// Example subscriber for the observer's memory pressure notifications
+//
+// Usage: cargo run --example pressure_subscriber -- <SOCKET> [COUNT]
+//
+// Connects to the notify_socket configured in ram_observer_config.json and reacts to
+// each pressure level the way an application with an in-memory cache would. With COUNT,
+// it exits after that many notifications, which makes it usable from test scripts.
+
+// Profile requests are only sent by the observer's --profile command
+#[allow(dead_code)]
+#[path = "../src/components/notify_client.rs"]
+mod notify_client;
+
+#[cfg(unix)]
+fn main() -> std::io::Result<()> {
+    use std::{collections::HashMap, env, process};
+
+    use notify_client::{PressureClient, PressureLevel};
+
+    let mut args = env::args().skip(1);
+    let Some(socket) = args.next() else {
+        eprintln!("Usage: pressure_subscriber <SOCKET> [COUNT]");
+        process::exit(2);
+    };
+    let limit: Option<usize> = args.next().and_then(|count| count.parse().ok());
+
+    // Stand-in for an application cache
+    let mut cache: HashMap<u32, Vec<u8>> = (0..64).map(|key| (key, vec![0; 1024 * 1024])).collect();
+
+    let mut client = PressureClient::connect(&socket, "pressure-subscriber")?;
+    let mut received = 0;
+    while let Some(notification) = client.recv()? {
+        received += 1;
+        match notification.level {
+            PressureLevel::Normal => {}
+            PressureLevel::Moderate => cache.retain(|key, _| key % 2 == 0),
+            PressureLevel::Critical => cache.clear(),
+        }
+        println!(
+            "{} pressure at {:.1}% (threshold {}%, profile {}), {} cache entries left",
+            notification.level.display_name(),
+            notification.percentage,
+            notification.threshold,
+            notification.profile,
+            cache.len()
+        );
+
+        if limit.is_some_and(|limit| received >= limit) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("Pressure notifications require Unix domain sockets");
+}
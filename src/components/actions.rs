@@ -1,17 +1,22 @@
 use std::{
     fs,
     io::{self, Write},
-    path::Path,
-    process::Command,
+    process::{Child, Command},
     time::Instant,
 };
 
 use crate::components::{
-    constants::AUTO_EXECUTION_COOLDOWN_SECS, memory_management::Commands, structs::RamMonitor,
+    cgroup::read_cgroup,
+    constants::{AUTO_EXECUTION_COOLDOWN_SECS, CGROUP_RECLAIM_PERCENT, RAMMAP_EXECUTABLE},
+    hugepages::ThpMode,
+    memory_management::{Action, CgroupCommands, Commands, KernelCommands},
+    procfs::read_trimmed,
+    structs::RamMonitor,
+    utils::bytes_to_gb,
 };
 
 impl RamMonitor {
-    /// Downloads and extracts RAMMap64.exe if it doesn't exist in the current directory
+    /// Downloads and extracts RAMMap64.exe if it doesn't exist in the data directory
     ///
     /// # Arguments
     /// * `self` - Mutable reference to RamMonitor instance
@@ -25,8 +30,15 @@ impl RamMonitor {
     /// 3. Extracts RAMMap64.exe from the zip
     /// 4. Cleans up temporary files
     pub fn ensure_rammap_exists(&mut self) -> io::Result<()> {
-        if !Path::new("RAMMap64.exe").exists() {
-            self.add_log("RAMMap64.exe not found. Downloading...".to_string(), false);
+        let rammap = self.paths.rammap();
+        if !rammap.exists() {
+            self.add_log(
+                format!("{RAMMAP_EXECUTABLE} not found. Downloading..."),
+                false,
+            );
+            if !self.paths.data_dir.as_os_str().is_empty() {
+                fs::create_dir_all(&self.paths.data_dir)?;
+            }
 
             // Download the zip file
             let response =
@@ -38,11 +50,12 @@ impl RamMonitor {
                 .map_err(|e| io::Error::other(e.to_string()))?;
 
             // Save zip file temporarily
-            let mut temp_file = fs::File::create("rammap_temp.zip")?;
+            let temp_path = self.paths.data_dir.join("rammap_temp.zip");
+            let mut temp_file = fs::File::create(&temp_path)?;
             temp_file.write_all(&bytes)?;
 
             // Extract RAMMap64.exe from the zip
-            let file = fs::File::open("rammap_temp.zip")?;
+            let file = fs::File::open(&temp_path)?;
             let mut archive =
                 zip::ZipArchive::new(file).map_err(|e| io::Error::other(e.to_string()))?;
 
@@ -53,16 +66,19 @@ impl RamMonitor {
                     .map_err(|e| io::Error::other(e.to_string()))?;
 
                 // Find and extract RAMMap64.exe
-                if file.name() == "RAMMap64.exe" {
-                    let mut outfile = fs::File::create("RAMMap64.exe")?;
+                if file.name() == RAMMAP_EXECUTABLE {
+                    let mut outfile = fs::File::create(&rammap)?;
                     io::copy(&mut file, &mut outfile)?;
                     break;
                 }
             }
 
             // Clean up the temporary zip file
-            fs::remove_file("rammap_temp.zip")?;
-            self.add_log("Successfully downloaded RAMMap64.exe".to_string(), false);
+            fs::remove_file(&temp_path)?;
+            self.add_log(
+                format!("Successfully downloaded {}", rammap.display()),
+                false,
+            );
         }
         Ok(())
     }
@@ -75,25 +91,35 @@ impl RamMonitor {
     ///
     /// # Behavior
     /// * Executes the configured auto-action if:
-    ///   1. Current RAM usage exceeds auto_threshold
+    ///   1. Current RAM usage exceeds auto_threshold, or the forecast time
+    ///      to reach it drops below the configured lead time
     ///   2. Enough time has passed since last auto-execution
     pub fn check_auto_execution(&mut self, current_percentage: f32) {
-        if current_percentage >= self.auto_threshold
+        let forecast_due = self
+            .config
+            .forecast_lead_secs
+            .zip(self.forecast_time_to(self.auto_threshold))
+            .map(|(lead, eta)| eta.as_secs() <= lead);
+
+        if (current_percentage >= self.auto_threshold || forecast_due.unwrap_or(false))
             && self
                 .last_auto_execution
                 .map(|time| time.elapsed().as_secs() > AUTO_EXECUTION_COOLDOWN_SECS)
                 .unwrap_or(true)
         {
-            let action = match self.auto_action.as_str() {
-                "Empty Working Sets" => Commands::EmptyWorkingSets,
-                "Empty System Working Sets" => Commands::EmptySystemWorkingSets,
-                "Empty Modified Page Lists" => Commands::EmptyModifiedPageLists,
-                "Empty Standby List" => Commands::EmptyStandbyList,
-                "Empty Priority 0 Standby List" => Commands::EmptyPriorityZeroStandbyList,
-                _ => Commands::EmptyWorkingSets,
-            };
-
-            self.run_rammap(action);
+            let action = Action::from_id(&self.auto_action, &self.config)
+                .unwrap_or(Action::RamMap(Commands::EmptyWorkingSets));
+
+            if current_percentage < self.auto_threshold {
+                self.add_log(
+                    format!(
+                        "Forecast reaches {}% within lead time, executing pre-emptively",
+                        self.auto_threshold
+                    ),
+                    false,
+                );
+            }
+            self.execute_action(action);
             self.last_auto_execution = Some(Instant::now());
         }
     }
@@ -112,22 +138,161 @@ impl RamMonitor {
     /// # Note
     /// This function will attempt to download RAMMap64.exe if it's not found
     pub fn run_rammap(&mut self, action: Commands) {
+        if self.spawn_rammap(action).is_some() {
+            self.add_log(
+                format!("Successfully executed: {}", action.display_name()),
+                false,
+            );
+            self.schedule_prewarm();
+        }
+    }
+
+    /// Starts RAMMap64.exe with the specified command, downloading it first if necessary
+    ///
+    /// # Returns
+    /// * `Some(Child)` for the running RAMMap process
+    /// * `None` if RAMMap could not be downloaded or started, after logging why
+    pub fn spawn_rammap(&mut self, action: Commands) -> Option<Child> {
         if let Err(e) = self.ensure_rammap_exists() {
             self.add_log(format!("Failed to download RAMMap: {e}"), true);
-            return;
+            return None;
         }
 
         // Execute the command
         let display_name = action.display_name();
         self.add_log(format!("Executing: {display_name}..."), false);
-        match Command::new("RAMMap64.exe").arg(action.parameter()).spawn() {
-            Ok(_) => {
-                self.add_log(format!("Successfully executed: {display_name}"), false);
-            }
+        match Command::new(self.paths.rammap())
+            .arg(action.parameter())
+            .spawn()
+        {
+            Ok(child) => Some(child),
             Err(e) => {
                 let error_msg = format!("Failed to execute RAMMap64: {e}");
                 self.add_log(error_msg, true);
+                None
+            }
+        }
+    }
+
+    /// Executes a memory management command against a single cgroup
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `action` - The cgroup command to execute
+    ///
+    /// # Process
+    /// 1. Writes the command's value to the group's control file
+    /// 2. Logs the result, including how much memory.current dropped
+    pub fn run_cgroup_command(&mut self, action: CgroupCommands) {
+        let root = self.cgroup_root();
+        let group = action.group();
+        let control_file = action.control_file();
+        let display_name = action.display_name();
+        let before = read_cgroup(&root, group);
+        let group_name = before
+            .as_ref()
+            .map(|stats| stats.display_path(&root))
+            .unwrap_or_else(|| group.display().to_string());
+
+        self.add_log(
+            format!("Executing: {display_name} on {group_name}..."),
+            false,
+        );
+        match fs::write(&control_file, action.value()) {
+            Ok(()) => {
+                let after = read_cgroup(&root, group);
+                let freed = before
+                    .zip(after)
+                    .map(|(before, after)| before.current.saturating_sub(after.current))
+                    .unwrap_or(0);
+                self.add_log(
+                    format!(
+                        "Successfully executed: {display_name} on {group_name} ({:.2}GB freed)",
+                        bytes_to_gb(freed)
+                    ),
+                    false,
+                );
+                self.schedule_prewarm();
+            }
+            Err(e) => {
+                self.add_log(
+                    format!("Failed to write {}: {e}", control_file.display()),
+                    true,
+                );
             }
         }
     }
+
+    /// Reclaims CGROUP_RECLAIM_PERCENT of the memory charged to the group selected in the cgroup browser
+    pub fn reclaim_selected_cgroup(&mut self) {
+        let Some(stats) = self.selected_cgroup() else {
+            self.add_log("No cgroup selected".to_string(), true);
+            return;
+        };
+
+        let action = CgroupCommands::Reclaim {
+            group: stats.path.clone(),
+            bytes: stats.current / 100 * CGROUP_RECLAIM_PERCENT,
+        };
+        self.run_cgroup_command(action);
+        self.last_cgroup_tree_refresh = None;
+    }
+
+    /// Executes a Linux kernel memory management command
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `action` - The kernel command to execute
+    ///
+    /// # Process
+    /// 1. Determines the value to write (cycling commands read the current mode first)
+    /// 2. Writes it to the command's procfs/sysfs file
+    /// 3. Logs the result (success or failure)
+    pub fn run_kernel_command(&mut self, action: KernelCommands) {
+        let display_name = action.display_name();
+        let control_file = action.control_file();
+
+        let value = match action {
+            KernelCommands::CompactMemory => Some(String::from("1")),
+            KernelCommands::CycleThpEnabled | KernelCommands::CycleThpDefrag => {
+                read_trimmed(control_file)
+                    .and_then(|contents| ThpMode::parse(&contents))
+                    .and_then(|mode| mode.next().map(str::to_string))
+            }
+        };
+        let Some(value) = value else {
+            self.add_log(format!("Failed to read {control_file}"), true);
+            return;
+        };
+
+        self.add_log(format!("Executing: {display_name}..."), false);
+        match fs::write(control_file, &value) {
+            Ok(()) => {
+                let result = match action {
+                    KernelCommands::CompactMemory => String::new(),
+                    _ => format!(" (now {value})"),
+                };
+                self.add_log(
+                    format!("Successfully executed: {display_name}{result}"),
+                    false,
+                );
+                self.last_hugepages_refresh = None;
+            }
+            Err(e) => {
+                self.add_log(format!("Failed to write {control_file}: {e}"), true);
+            }
+        }
+    }
+
+    /// Executes any action from the Memory Management list
+    pub fn execute_action(&mut self, action: Action) {
+        match action {
+            Action::RamMap(cmd) => self.run_rammap(cmd),
+            Action::Kernel(cmd) => self.run_kernel_command(cmd),
+            Action::Prewarm => self.start_prewarm(),
+            Action::Evict(index) => self.run_eviction(index),
+            Action::Custom(index) => self.run_custom_action(index),
+            Action::Macro(index) => self.start_macro(index),
+        }
+    }
 }
@@ -0,0 +1,237 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use crate::components::{
+    constants::{CGROUP_REFRESH_MS, CGROUP_TREE_REFRESH_MS, DEFAULT_CGROUP_ROOT},
+    procfs::{find_value, read_trimmed, read_u64},
+    structs::{MemoryScope, RamMonitor},
+    utils::is_due,
+};
+
+/// Memory accounting for a single cgroup v2 group
+#[derive(Clone)]
+pub struct CgroupStats {
+    pub path: PathBuf,
+    pub current: u64,
+    pub max: Option<u64>,
+    pub limit: Option<u64>,
+    pub swap_current: Option<u64>,
+    pub inactive_file: u64,
+    pub pressure_avg10: Option<f32>,
+    pub oom: u64,
+    pub oom_kill: u64,
+}
+
+/// A group in the cgroup tree along with its nesting depth below the root
+pub struct CgroupNode {
+    pub depth: usize,
+    pub stats: CgroupStats,
+}
+
+impl CgroupStats {
+    /// Memory in use, excluding inactive page cache that can be reclaimed for free
+    pub fn used(&self) -> u64 {
+        self.current.saturating_sub(self.inactive_file)
+    }
+
+    /// Name of the group relative to the cgroup root, for display
+    pub fn display_path(&self, root: &Path) -> String {
+        match self.path.strip_prefix(root) {
+            Ok(relative) if relative.as_os_str().is_empty() => String::from("/"),
+            Ok(relative) => format!("/{}", relative.display()),
+            Err(_) => self.path.display().to_string(),
+        }
+    }
+}
+
+/// Parses a memory.max style value, where "max" means unlimited
+pub fn parse_limit(value: &str) -> Option<u64> {
+    match value {
+        "max" => None,
+        value => value.parse().ok(),
+    }
+}
+
+/// Resolves the cgroup v2 directory the observer itself runs in
+///
+/// # Arguments
+/// * `root` - The cgroup v2 mount point
+///
+/// # Returns
+/// * The group directory from /proc/self/cgroup, or the root itself if that
+///   group doesn't exist below `root`
+/// * `None` if `root` isn't a cgroup v2 hierarchy
+pub fn own_cgroup_dir(root: &Path) -> Option<PathBuf> {
+    if !root.join("cgroup.controllers").exists() {
+        return None;
+    }
+
+    match process_cgroup("self") {
+        Some(relative) if root.join(&relative).join("memory.current").exists() => {
+            Some(root.join(relative))
+        }
+        _ => Some(root.to_path_buf()),
+    }
+}
+
+/// Reads a process's cgroup v2 group from /proc/<pid>/cgroup
+///
+/// # Arguments
+/// * `pid` - Process id, or "self"
+///
+/// # Returns
+/// * The group path relative to the cgroup v2 root, e.g. "user.slice/user-1000.slice"
+pub fn process_cgroup(pid: &str) -> Option<String> {
+    read_trimmed(format!("/proc/{pid}/cgroup")).and_then(|contents| {
+        contents
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .map(|path| path.trim_start_matches('/').to_string())
+    })
+}
+
+/// Parses the "some avg10=" value from a memory.pressure file
+fn parse_pressure_avg10(contents: &str) -> Option<f32> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("some "))?
+        .split_whitespace()
+        .find_map(|field| field.strip_prefix("avg10="))?
+        .parse()
+        .ok()
+}
+
+/// Finds the tightest memory.max between `dir` and the cgroup root
+fn effective_limit(root: &Path, dir: &Path) -> Option<u64> {
+    dir.ancestors()
+        .take_while(|ancestor| ancestor.starts_with(root))
+        .filter_map(|ancestor| read_trimmed(ancestor.join("memory.max")))
+        .filter_map(|value| parse_limit(&value))
+        .min()
+}
+
+/// Reads memory.current, memory.max, memory.stat and memory.events for a group
+///
+/// # Returns
+/// * `None` if the group has no memory controller files
+pub fn read_cgroup(root: &Path, dir: &Path) -> Option<CgroupStats> {
+    let current = read_u64(dir.join("memory.current"))?;
+    let stat = fs::read_to_string(dir.join("memory.stat")).unwrap_or_default();
+    let events = fs::read_to_string(dir.join("memory.events")).unwrap_or_default();
+
+    Some(CgroupStats {
+        path: dir.to_path_buf(),
+        current,
+        max: read_trimmed(dir.join("memory.max")).and_then(|value| parse_limit(&value)),
+        limit: effective_limit(root, dir),
+        swap_current: read_u64(dir.join("memory.swap.current")),
+        inactive_file: find_value(&stat, "inactive_file").unwrap_or(0),
+        pressure_avg10: read_trimmed(dir.join("memory.pressure"))
+            .and_then(|contents| parse_pressure_avg10(&contents)),
+        oom: find_value(&events, "oom").unwrap_or(0),
+        oom_kill: find_value(&events, "oom_kill").unwrap_or(0),
+    })
+}
+
+/// Walks the cgroup hierarchy depth-first, collecting every group with a memory controller
+///
+/// # Arguments
+/// * `root` - The cgroup v2 mount point
+/// * `dir` - The directory to walk from
+/// * `depth` - Nesting depth of `dir` below the root
+/// * `nodes` - Output list, in display order
+fn collect_cgroups(root: &Path, dir: &Path, depth: usize, nodes: &mut Vec<CgroupNode>) {
+    if let Some(stats) = read_cgroup(root, dir) {
+        nodes.push(CgroupNode { depth, stats });
+    }
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut children: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|entry| entry.path())
+        .collect();
+    children.sort();
+
+    for child in children {
+        collect_cgroups(root, &child, depth + 1, nodes);
+    }
+}
+
+impl RamMonitor {
+    /// Returns the configured cgroup v2 mount point
+    pub fn cgroup_root(&self) -> PathBuf {
+        PathBuf::from(
+            self.config
+                .cgroup_root
+                .as_deref()
+                .unwrap_or(DEFAULT_CGROUP_ROOT),
+        )
+    }
+
+    /// Re-reads the enclosing cgroup's memory accounting at most once per CGROUP_REFRESH_MS
+    pub fn refresh_cgroup(&mut self) {
+        if !is_due(self.last_cgroup_refresh, CGROUP_REFRESH_MS) {
+            return;
+        }
+
+        let root = self.cgroup_root();
+        self.cgroup = own_cgroup_dir(&root).and_then(|dir| read_cgroup(&root, &dir));
+        self.last_cgroup_refresh = Some(Instant::now());
+    }
+
+    /// Re-walks the whole cgroup tree at most once per CGROUP_TREE_REFRESH_MS
+    pub fn refresh_cgroup_tree(&mut self) {
+        if !is_due(self.last_cgroup_tree_refresh, CGROUP_TREE_REFRESH_MS) {
+            return;
+        }
+
+        let root = self.cgroup_root();
+        let mut nodes = Vec::new();
+        collect_cgroups(&root, &root, 0, &mut nodes);
+        self.cgroup_tree = nodes;
+        self.last_cgroup_tree_refresh = Some(Instant::now());
+    }
+
+    /// Returns the group currently selected in the cgroup browser, if any
+    pub fn selected_cgroup(&self) -> Option<&CgroupStats> {
+        self.cgroup_tree
+            .get(self.view_selection)
+            .map(|node| &node.stats)
+    }
+
+    /// Switches the RAM gauge between host-wide and cgroup-relative usage
+    /// The forecast samples are dropped, as they measure usage against the other total
+    pub fn toggle_memory_scope(&mut self) {
+        self.refresh_cgroup();
+        let was_cgroup = matches!(self.memory_scope, MemoryScope::Cgroup);
+        self.memory_scope = match self.memory_scope {
+            MemoryScope::Host if self.cgroup.is_some() => {
+                self.add_log("Memory view changed to: cgroup".to_string(), false);
+                MemoryScope::Cgroup
+            }
+            MemoryScope::Host => {
+                self.add_log(
+                    format!(
+                        "No cgroup v2 hierarchy found at {}",
+                        self.cgroup_root().display()
+                    ),
+                    true,
+                );
+                MemoryScope::Host
+            }
+            MemoryScope::Cgroup => {
+                self.add_log("Memory view changed to: host".to_string(), false);
+                MemoryScope::Host
+            }
+        };
+        if matches!(self.memory_scope, MemoryScope::Cgroup) != was_cgroup {
+            self.history.clear();
+        }
+    }
+}
@@ -0,0 +1,343 @@
+use serde_json::Value;
+use std::{path::PathBuf, time::Duration};
+
+#[cfg(unix)]
+use crate::components::notify_client::PressureClient;
+use crate::components::{
+    constants::{DEFAULT_PROFILE, PROFILE_SWITCH_TIMEOUT_SECS},
+    eviction::{EvictionFilter, describe_result, evict},
+    paths::Paths,
+    structs::Config,
+};
+
+/// Usage text printed for --help and on invalid arguments
+pub const USAGE: &str = "\
+Usage: ram-observer-rs [--config <PATH>] [COMMAND]
+
+Without a command the interactive monitor is started.
+
+Options:
+  --config <PATH>           Use this config file instead of looking one up
+                            (also set with RAM_OBSERVER_CONFIG)
+
+Commands:
+  --evict <PATH>...         Evict the page cache of files below the given paths
+      --include <GLOB>      Only evict files matching the pattern (repeatable)
+      --exclude <GLOB>      Skip files matching the pattern (repeatable)
+  --evict-target <NAME>     Evict a target from the config's eviction_targets
+  --subscribe [NAME]        Print pressure notifications from a running observer
+  --check-config [PATH]     Validate a config file without changing it (.json or .toml)
+  --profile <NAME>          Switch the running observer and the config to a profile
+                            (\"default\" for the settings outside any profile)
+  --help                    Print this help";
+
+/// A command run from the command line instead of starting the TUI
+pub enum CliCommand {
+    Help,
+    Evict {
+        paths: Vec<String>,
+        include: Vec<String>,
+        exclude: Vec<String>,
+    },
+    EvictTarget(String),
+    Subscribe(String),
+    CheckConfig(Option<String>),
+    SwitchProfile(String),
+}
+
+/// Parsed command-line arguments
+pub struct CliArgs {
+    /// Command to run headless, or None to start the TUI
+    pub command: Option<CliCommand>,
+    /// Config file given with --config
+    pub config: Option<String>,
+}
+
+/// Takes the value following a flag
+fn flag_value(flag: &str, args: &mut impl Iterator<Item = String>) -> Result<String, String> {
+    args.next()
+        .filter(|value| !value.starts_with("--"))
+        .ok_or_else(|| format!("{flag} requires a value"))
+}
+
+/// Parses command-line arguments, excluding the program name
+///
+/// # Returns
+/// * `Ok(CliArgs)` with the command to run headless, if any, and the config file to use
+/// * `Err(String)` describing invalid arguments
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<CliArgs, String> {
+    let mut args = args.into_iter().peekable();
+    let mut command = None;
+    let mut config = None;
+    let mut include = Vec::new();
+    let mut exclude = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--help" | "-h" => {
+                return Ok(CliArgs {
+                    command: Some(CliCommand::Help),
+                    config,
+                });
+            }
+            "--config" => config = Some(flag_value(&arg, &mut args)?),
+            "--evict" => {
+                let mut paths = Vec::new();
+                while let Some(path) = args.next_if(|arg| !arg.starts_with("--")) {
+                    paths.push(path);
+                }
+                if paths.is_empty() {
+                    return Err("--evict requires at least one path".to_string());
+                }
+                command = Some(CliCommand::Evict {
+                    paths,
+                    include: Vec::new(),
+                    exclude: Vec::new(),
+                });
+            }
+            "--evict-target" => {
+                command = Some(CliCommand::EvictTarget(flag_value(&arg, &mut args)?));
+            }
+            "--subscribe" => {
+                let name = args
+                    .next_if(|arg| !arg.starts_with("--"))
+                    .unwrap_or_else(|| "ram-observer-cli".to_string());
+                command = Some(CliCommand::Subscribe(name));
+            }
+            "--check-config" => {
+                command = Some(CliCommand::CheckConfig(
+                    args.next_if(|arg| !arg.starts_with("--")),
+                ));
+            }
+            "--profile" => {
+                command = Some(CliCommand::SwitchProfile(flag_value(&arg, &mut args)?));
+            }
+            "--include" => include.push(flag_value(&arg, &mut args)?),
+            "--exclude" => exclude.push(flag_value(&arg, &mut args)?),
+            _ => return Err(format!("Unknown argument: {arg}")),
+        }
+    }
+
+    match &mut command {
+        Some(CliCommand::Evict {
+            include: evict_include,
+            exclude: evict_exclude,
+            ..
+        }) => {
+            *evict_include = include;
+            *evict_exclude = exclude;
+        }
+        _ if !include.is_empty() || !exclude.is_empty() => {
+            return Err("--include and --exclude can only be used with --evict".to_string());
+        }
+        _ => {}
+    }
+
+    Ok(CliArgs { command, config })
+}
+
+/// Connects to the configured notify_socket and prints every notification as a JSON line
+#[cfg(unix)]
+fn subscribe(name: &str, paths: &Paths) -> i32 {
+    let (config, _) = Config::read_from(&paths.config, true);
+    let Some(path) = config.notify_socket else {
+        eprintln!("No notify_socket configured");
+        return 1;
+    };
+
+    let mut client = match PressureClient::connect(&path, name) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to connect to {path}: {e}");
+            return 1;
+        }
+    };
+    loop {
+        match client.recv() {
+            Ok(Some(notification)) => match serde_json::to_string(&notification) {
+                Ok(line) => println!("{line}"),
+                Err(e) => eprintln!("Invalid notification: {e}"),
+            },
+            Ok(None) => return 0,
+            Err(e) => {
+                eprintln!("Connection to {path} failed: {e}");
+                return 1;
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn subscribe(_name: &str, _paths: &Paths) -> i32 {
+    eprintln!("Pressure notifications require Unix domain sockets");
+    1
+}
+
+/// Validates a config file and prints every message with its location
+///
+/// # Arguments
+/// * `path` - The file to check, or None for the resolved config file
+/// * `paths` - The resolved paths
+///
+/// # Returns
+/// * 0 if the config has no errors, 1 otherwise
+fn check_config(path: Option<String>, paths: &Paths) -> i32 {
+    let path = path.map_or_else(|| paths.config.clone(), PathBuf::from);
+    if !path.exists() {
+        eprintln!("No config file at {}", path.display());
+        return 1;
+    }
+
+    let (_, messages) = Config::read_from(&path, false);
+    let errors = messages.iter().filter(|(_, is_error)| *is_error).count();
+    for (msg, is_error) in &messages {
+        let level = if *is_error { "error" } else { "note" };
+        println!("{level}: {msg}");
+    }
+    match errors {
+        0 => {
+            println!("{} is valid", path.display());
+            0
+        }
+        1 => {
+            println!("{}: 1 error", path.display());
+            1
+        }
+        errors => {
+            println!("{}: {errors} errors", path.display());
+            1
+        }
+    }
+}
+
+/// Asks the observer listening on the notify socket to switch profiles
+///
+/// # Returns
+/// * `Ok(true)` once the observer confirmed the switch
+/// * `Ok(false)` if no observer is listening
+/// * `Err(String)` if the observer didn't confirm the switch in time
+#[cfg(unix)]
+fn request_profile(socket: &str, name: &str) -> Result<bool, String> {
+    let Ok(mut client) = PressureClient::request_profile(socket, "ram-observer-cli", name) else {
+        return Ok(false);
+    };
+    client
+        .set_timeout(Some(Duration::from_secs(PROFILE_SWITCH_TIMEOUT_SECS)))
+        .map_err(|e| e.to_string())?;
+    loop {
+        match client.recv() {
+            Ok(Some(notification)) if notification.profile == name => return Ok(true),
+            Ok(Some(_)) => {}
+            Ok(None) => return Err("the observer closed the connection".to_string()),
+            Err(e) => return Err(format!("no confirmation from the observer ({e})")),
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn request_profile(_socket: &str, _name: &str) -> Result<bool, String> {
+    Ok(false)
+}
+
+/// Switches profiles: through the notify socket if an observer is listening, which saves
+/// the choice itself, otherwise by setting active_profile in the config file
+///
+/// # Returns
+/// * 0 if the profile is now active, 1 otherwise
+fn switch_profile(name: &str, paths: &Paths) -> i32 {
+    let (config, _) = Config::read_from(&paths.config, true);
+    if name != DEFAULT_PROFILE && config.profile_index(name).is_none() {
+        eprintln!("No profile named {name} in {}", paths.config.display());
+        return 1;
+    }
+
+    if let Some(socket) = &config.notify_socket {
+        match request_profile(socket, name) {
+            Ok(true) => {
+                println!("Switched the running observer to profile {name}");
+                return 0;
+            }
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to switch profile through {socket}: {e}");
+                return 1;
+            }
+        }
+    }
+
+    let active_profile = (name != DEFAULT_PROFILE).then(|| name.to_string());
+    if active_profile == config.active_profile {
+        println!("Profile {name} is already active");
+        return 0;
+    }
+    let value = active_profile.map_or(Value::Null, Value::from);
+    match Config::save_field(&paths.config, "active_profile", &value) {
+        Ok(_) => {
+            println!("Set active_profile to {name} in {}", paths.config.display());
+            0
+        }
+        Err(e) => {
+            eprintln!("Failed to save {}: {e}", paths.config.display());
+            1
+        }
+    }
+}
+
+/// Runs a command-line command, printing its result
+///
+/// # Arguments
+/// * `command` - The command to run
+/// * `paths` - Resolved locations of the config file and the data directory
+///
+/// # Returns
+/// * The process exit code
+pub fn run(command: CliCommand, paths: &Paths) -> i32 {
+    match command {
+        CliCommand::Help => {
+            println!("{USAGE}");
+            0
+        }
+        CliCommand::Evict {
+            paths,
+            include,
+            exclude,
+        } => match EvictionFilter::new(&paths, &include, &exclude) {
+            Ok(filter) => {
+                println!("{}", describe_result(&paths.join(", "), &evict(&filter)));
+                0
+            }
+            Err(e) => {
+                eprintln!("Invalid pattern: {e}");
+                2
+            }
+        },
+        CliCommand::Subscribe(name) => subscribe(&name, paths),
+        CliCommand::CheckConfig(path) => check_config(path, paths),
+        CliCommand::SwitchProfile(name) => switch_profile(&name, paths),
+        CliCommand::EvictTarget(name) => {
+            let (config, messages) = Config::read_from(&paths.config, true);
+            for (msg, _) in messages {
+                eprintln!("{msg}");
+            }
+
+            let Some(target) = config
+                .eviction_targets
+                .iter()
+                .find(|target| target.name == name)
+            else {
+                eprintln!("No eviction target named {name} in the config");
+                return 1;
+            };
+            match EvictionFilter::from_target(target) {
+                Ok(filter) => {
+                    println!("{}", describe_result(&name, &evict(&filter)));
+                    0
+                }
+                Err(e) => {
+                    eprintln!("Invalid pattern in {name}: {e}");
+                    2
+                }
+            }
+        }
+    }
+}
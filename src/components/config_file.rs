@@ -0,0 +1,767 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use toml_edit::{DocumentMut, Item, Table};
+
+use std::{
+    collections::HashMap,
+    io, mem,
+    path::{Path, PathBuf},
+};
+
+use crate::components::config_handler::ValidationMessage;
+
+/// File formats the config can be written in, chosen by file extension
+#[derive(Clone, Copy, PartialEq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Picks the format from the file extension, defaulting to JSON
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+
+    /// Parses the file's top-level table
+    ///
+    /// # Returns
+    /// * `Ok(Map)` with the file's fields
+    /// * `Err((String, Option<usize>))` with the parser's message and the byte offset of the error
+    pub fn parse(&self, contents: &str) -> Result<Map<String, Value>, (String, Option<usize>)> {
+        match self {
+            Self::Json => serde_json::from_str(contents).map_err(|e| {
+                let offset = line_offset(contents, e.line()).map(|offset| offset + e.column());
+                (
+                    strip_position(&e.to_string()),
+                    offset.map(|o| o.saturating_sub(1)),
+                )
+            }),
+            Self::Toml => toml::from_str(contents)
+                .map_err(|e| (e.message().to_string(), e.span().map(|span| span.start))),
+        }
+    }
+
+    /// Serializes a value as the contents of a config file
+    pub fn serialize<T: Serialize>(&self, value: &T) -> io::Result<String> {
+        match self {
+            Self::Json => serde_json::to_string_pretty(value).map_err(io::Error::from),
+            Self::Toml => toml::to_string_pretty(value).map_err(io::Error::other),
+        }
+    }
+}
+
+/// A config file's own document, edited in place when the config is written back so that
+/// comments, formatting and fields the observer doesn't use are kept
+pub enum ConfigDocument {
+    Json(Map<String, Value>),
+    Toml(DocumentMut),
+}
+
+impl ConfigDocument {
+    /// Parses a config file's contents in the given format
+    pub fn parse(format: ConfigFormat, contents: &str) -> io::Result<Self> {
+        match format {
+            ConfigFormat::Json => serde_json::from_str(contents)
+                .map(Self::Json)
+                .map_err(io::Error::from),
+            ConfigFormat::Toml => contents
+                .parse()
+                .map(Self::Toml)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        }
+    }
+
+    /// Replaces a top-level field, or removes it when the value is null
+    /// In TOML only the values that differ are replaced, so the rest of the field keeps
+    /// its layout and comments
+    pub fn set(&mut self, key: &str, value: &Value) -> io::Result<()> {
+        let value = shortest_floats(value.clone());
+        match self {
+            Self::Json(fields) if value.is_null() => {
+                fields.remove(key);
+            }
+            Self::Json(fields) => {
+                fields.insert(key.to_string(), value);
+            }
+            Self::Toml(document) => update_table(document.as_table_mut(), key, &value)?,
+        }
+        Ok(())
+    }
+
+    /// Sets a field of the list entry with the given name, e.g. the threshold of one profile,
+    /// or removes it when the value is null
+    ///
+    /// # Arguments
+    /// * `list` - The top-level list, such as "profiles"
+    /// * `name` - The `name` of the entry
+    /// * `key` - The entry's field to set
+    /// * `value` - Its new value
+    pub fn set_entry(
+        &mut self,
+        list: &str,
+        name: &str,
+        key: &str,
+        value: &Value,
+    ) -> io::Result<()> {
+        let value = shortest_floats(value.clone());
+        let not_found = || {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no {list} entry named {name}"),
+            )
+        };
+        match self {
+            Self::Json(fields) => {
+                let entry = fields
+                    .get_mut(list)
+                    .and_then(Value::as_array_mut)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_object_mut)
+                    .find(|entry| entry.get("name").and_then(Value::as_str) == Some(name))
+                    .ok_or_else(not_found)?;
+                if value.is_null() {
+                    entry.remove(key);
+                } else {
+                    entry.insert(key.to_string(), value);
+                }
+            }
+            Self::Toml(document) => match document.get_mut(list) {
+                Some(Item::ArrayOfTables(tables)) => {
+                    let table = tables
+                        .iter_mut()
+                        .find(|table| table.get("name").and_then(Item::as_str) == Some(name))
+                        .ok_or_else(not_found)?;
+                    update_table(table, key, &value)?;
+                }
+                Some(Item::Value(toml_edit::Value::Array(entries))) => {
+                    let table = entries
+                        .iter_mut()
+                        .filter_map(toml_edit::Value::as_inline_table_mut)
+                        .find(|table| {
+                            table.get("name").and_then(toml_edit::Value::as_str) == Some(name)
+                        })
+                        .ok_or_else(not_found)?;
+                    match table.get_mut(key) {
+                        _ if value.is_null() => {
+                            table.remove(key);
+                        }
+                        Some(existing) => update_value(existing, &value)?,
+                        None => {
+                            table.insert(key, toml_value(&value)?);
+                        }
+                    }
+                }
+                _ => return Err(not_found()),
+            },
+        }
+        Ok(())
+    }
+
+    /// Returns the contents to write back to the file
+    pub fn contents(&self) -> io::Result<String> {
+        match self {
+            Self::Json(fields) => serde_json::to_string_pretty(fields).map_err(io::Error::from),
+            Self::Toml(document) => Ok(document.to_string()),
+        }
+    }
+}
+
+/// Sets a key of a TOML table, or removes it when the value is null
+fn update_table(table: &mut Table, key: &str, value: &Value) -> io::Result<()> {
+    if value.is_null() {
+        table.remove(key);
+        return Ok(());
+    }
+    match table.get_mut(key) {
+        Some(item) => update_item(item, value),
+        None => {
+            table.insert(key, toml_item(value)?);
+            Ok(())
+        }
+    }
+}
+
+/// Updates a TOML item to a value, descending into tables and lists of the same shape
+fn update_item(item: &mut Item, value: &Value) -> io::Result<()> {
+    match (item, value) {
+        (Item::Table(table), Value::Object(fields)) => {
+            let removed: Vec<String> = table
+                .iter()
+                .map(|(key, _)| key.to_string())
+                .filter(|key| !fields.contains_key(key))
+                .collect();
+            for key in removed {
+                table.remove(&key);
+            }
+            for (key, value) in fields {
+                update_table(table, key, value)?;
+            }
+            Ok(())
+        }
+        (Item::ArrayOfTables(tables), Value::Array(entries))
+            if tables.len() == entries.len() && entries.iter().all(Value::is_object) =>
+        {
+            for (table, entry) in tables.iter_mut().zip(entries) {
+                let mut item = Item::Table(mem::take(table));
+                update_item(&mut item, entry)?;
+                if let Item::Table(updated) = item {
+                    *table = updated;
+                }
+            }
+            Ok(())
+        }
+        (Item::Value(existing), value) => update_value(existing, value),
+        (item, value) => {
+            let mut replacement = toml_item(value)?;
+            keep_position(item, &mut replacement);
+            *item = replacement;
+            Ok(())
+        }
+    }
+}
+
+/// Updates a TOML value to a value, descending into inline tables and arrays of the same shape
+fn update_value(existing: &mut toml_edit::Value, value: &Value) -> io::Result<()> {
+    match (existing, value) {
+        (toml_edit::Value::InlineTable(table), Value::Object(fields)) => {
+            table.retain(|key, _| fields.get(key).is_some_and(|value| !value.is_null()));
+            for (key, value) in fields.iter().filter(|(_, value)| !value.is_null()) {
+                match table.get_mut(key) {
+                    Some(existing) => update_value(existing, value)?,
+                    None => {
+                        table.insert(key, toml_value(value)?);
+                    }
+                }
+            }
+            Ok(())
+        }
+        (toml_edit::Value::Array(array), Value::Array(entries)) if array.len() == entries.len() => {
+            for (existing, entry) in array.iter_mut().zip(entries) {
+                update_value(existing, entry)?;
+            }
+            Ok(())
+        }
+        (existing, value) if same_scalar(existing, value) => Ok(()),
+        (existing, value) => {
+            let decor = existing.decor().clone();
+            *existing = toml_value(value)?;
+            *existing.decor_mut() = decor;
+            Ok(())
+        }
+    }
+}
+
+/// Returns true when a TOML scalar holds the value, comparing floats at the f32 precision
+/// the config uses
+fn same_scalar(existing: &toml_edit::Value, value: &Value) -> bool {
+    match existing {
+        toml_edit::Value::String(string) => value.as_str() == Some(string.value().as_str()),
+        toml_edit::Value::Integer(integer) => value.as_i64() == Some(*integer.value()),
+        toml_edit::Value::Float(float) => value
+            .as_f64()
+            .is_some_and(|value| value as f32 == *float.value() as f32),
+        toml_edit::Value::Boolean(boolean) => value.as_bool() == Some(*boolean.value()),
+        _ => false,
+    }
+}
+
+/// Formats a value the way a fresh TOML config would, e.g. a list of tables as `[[key]]` entries
+fn toml_item(value: &Value) -> io::Result<Item> {
+    let mut table = Map::new();
+    table.insert("value".to_string(), without_nulls(value.clone()));
+    let rendered = toml::to_string_pretty(&table).map_err(io::Error::other)?;
+    let mut document: DocumentMut = rendered
+        .parse()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let mut item = document.remove("value").unwrap_or_default();
+
+    // The spacing around the value belongs to the document it was parsed from, as do the
+    // positions of tables
+    if let Some(value) = item.as_value_mut() {
+        value.decor_mut().clear();
+    }
+    clear_positions(&mut item);
+    Ok(item)
+}
+
+/// Formats a value to be placed inside an inline table or an array
+fn toml_value(value: &Value) -> io::Result<toml_edit::Value> {
+    toml_item(value)?
+        .into_value()
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "value can't be inlined"))
+}
+
+/// Places a replacement where the item it replaces was, with the comments before it
+fn keep_position(existing: &Item, replacement: &mut Item) {
+    let first = match existing {
+        Item::Table(table) => Some(table),
+        Item::ArrayOfTables(tables) => tables.iter().next(),
+        _ => None,
+    };
+    let Some(first) = first else {
+        return;
+    };
+    let tables: Vec<&mut Table> = match replacement {
+        Item::Table(table) => vec![table],
+        Item::ArrayOfTables(tables) => tables.iter_mut().collect(),
+        _ => Vec::new(),
+    };
+    for (index, table) in tables.into_iter().enumerate() {
+        table.set_position(first.position());
+        if index == 0 {
+            *table.decor_mut() = first.decor().clone();
+        }
+    }
+}
+
+/// Clears the position of every table in an item, so each one is placed after the table
+/// before it in the document
+fn clear_positions(item: &mut Item) {
+    let tables: Vec<&mut Table> = match item {
+        Item::Table(table) => vec![table],
+        Item::ArrayOfTables(tables) => tables.iter_mut().collect(),
+        _ => return,
+    };
+    for table in tables {
+        table.set_position(None);
+        for (_, item) in table.iter_mut() {
+            clear_positions(item);
+        }
+    }
+}
+
+/// Removes null values, which TOML can't represent and which stand for unset options
+fn without_nulls(value: Value) -> Value {
+    match value {
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, without_nulls(value)))
+                .collect(),
+        ),
+        Value::Array(entries) => Value::Array(entries.into_iter().map(without_nulls).collect()),
+        value => value,
+    }
+}
+
+/// Writes floats that came from f32 settings with their shortest f32 digits,
+/// so 72.3 isn't saved as 72.30000305175781
+fn shortest_floats(value: Value) -> Value {
+    match value {
+        Value::Number(number) => {
+            let shortest = number
+                .as_f64()
+                .filter(|_| number.is_f64())
+                .filter(|float| f64::from(*float as f32) == *float)
+                .and_then(|float| (float as f32).to_string().parse().ok())
+                .and_then(serde_json::Number::from_f64);
+            Value::Number(shortest.unwrap_or(number))
+        }
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, shortest_floats(value)))
+                .collect(),
+        ),
+        Value::Array(entries) => Value::Array(entries.into_iter().map(shortest_floats).collect()),
+        value => value,
+    }
+}
+
+/// A message about the config, naming the field it concerns so it can be located in the file
+pub struct ConfigMessage {
+    pub field: Option<String>,
+    pub entry: Option<usize>,
+    pub text: String,
+    pub is_error: bool,
+}
+
+impl ConfigMessage {
+    /// A message about the config as a whole
+    pub fn new(text: String, is_error: bool) -> Self {
+        Self {
+            field: None,
+            entry: None,
+            text,
+            is_error,
+        }
+    }
+
+    /// A message about a top-level field
+    pub fn field(field: &str, text: String, is_error: bool) -> Self {
+        Self {
+            field: Some(field.to_string()),
+            ..Self::new(text, is_error)
+        }
+    }
+
+    /// A message about one entry of a list field
+    pub fn entry(field: &str, entry: usize, text: String, is_error: bool) -> Self {
+        Self {
+            entry: Some(entry),
+            ..Self::field(field, text, is_error)
+        }
+    }
+}
+
+/// The contents of a loaded config file, used to point messages at the offending lines
+pub struct ConfigSource {
+    pub path: PathBuf,
+    pub contents: String,
+    pub format: ConfigFormat,
+    /// Indices of list entries dropped while parsing, so later entry indices can be mapped
+    /// back to their position in the file
+    pub dropped: HashMap<String, Vec<usize>>,
+}
+
+impl ConfigSource {
+    /// Formats a message as `file:line:column: field[entry]: text`
+    /// The location is left out when the field can't be found in the file
+    pub fn render(&self, message: ConfigMessage) -> ValidationMessage {
+        let Some(field) = &message.field else {
+            return (message.text, message.is_error);
+        };
+
+        let entry = message.entry.map(|entry| self.file_entry(field, entry));
+        let key = match entry {
+            Some(entry) => format!("{field}[{entry}]"),
+            None => field.clone(),
+        };
+        let located = match self.format {
+            ConfigFormat::Json => locate_json(&self.contents, field, entry),
+            ConfigFormat::Toml => locate_toml(&self.contents, field, entry),
+        };
+        let text = match located {
+            Some(offset) => format!("{}: {key}: {}", self.position(offset), message.text),
+            None => format!("{}: {key}: {}", self.path.display(), message.text),
+        };
+        (text, message.is_error)
+    }
+
+    /// Formats a byte offset as `file:line:column`
+    pub fn position(&self, offset: usize) -> String {
+        let mut offset = offset.min(self.contents.len());
+        while !self.contents.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &self.contents[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.len() - before.rfind('\n').map_or(0, |newline| newline + 1) + 1;
+        format!("{}:{line}:{column}", self.path.display())
+    }
+
+    /// Maps an entry index of the parsed list to its index in the file
+    fn file_entry(&self, field: &str, entry: usize) -> usize {
+        let dropped = self.dropped.get(field).map_or(&[][..], Vec::as_slice);
+        (0..)
+            .filter(|index| !dropped.contains(index))
+            .nth(entry)
+            .unwrap_or(entry)
+    }
+}
+
+/// Formats messages that can't be located in a file, e.g. when saving
+pub fn render_unlocated(messages: Vec<ConfigMessage>) -> Vec<ValidationMessage> {
+    messages
+        .into_iter()
+        .map(|message| match (&message.field, message.entry) {
+            (Some(field), Some(entry)) => (
+                format!("{field}[{entry}]: {}", message.text),
+                message.is_error,
+            ),
+            (Some(field), None) => (format!("{field}: {}", message.text), message.is_error),
+            (None, _) => (message.text, message.is_error),
+        })
+        .collect()
+}
+
+/// Returns the byte offset at which the given 1-based line starts
+fn line_offset(contents: &str, line: usize) -> Option<usize> {
+    if line <= 1 {
+        return Some(0);
+    }
+    contents
+        .match_indices('\n')
+        .nth(line - 2)
+        .map(|(offset, _)| offset + 1)
+}
+
+/// Removes serde_json's " at line X column Y" suffix, as the position is reported separately
+fn strip_position(message: &str) -> String {
+    message
+        .rfind(" at line ")
+        .map_or(message, |index| &message[..index])
+        .to_string()
+}
+
+/// Finds a top-level key in a JSON document, or the start of one of its array entries
+fn locate_json(contents: &str, field: &str, entry: Option<usize>) -> Option<usize> {
+    let bytes = contents.as_bytes();
+    let mut depth = 0usize;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'"' => {
+                let end = string_end(bytes, index)?;
+                let is_key = depth == 1
+                    && contents[end + 1..].trim_start().starts_with(':')
+                    && &contents[index + 1..end] == field;
+                if is_key {
+                    return match entry {
+                        Some(entry) => locate_json_entry(contents, end + 1, entry).or(Some(index)),
+                        None => Some(index),
+                    };
+                }
+                index = end;
+            }
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Finds the start of an array entry, given the offset just after the array's key
+fn locate_json_entry(contents: &str, from: usize, entry: usize) -> Option<usize> {
+    let bytes = contents.as_bytes();
+    let mut index = from + contents[from..].find('[')? + 1;
+    let mut depth = 0usize;
+    let mut current = 0;
+    let mut expecting = true;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if expecting && !byte.is_ascii_whitespace() {
+            if current == entry {
+                return Some(index);
+            }
+            expecting = false;
+        }
+        match byte {
+            b'"' => index = string_end(bytes, index)?,
+            b'{' | b'[' => depth += 1,
+            b'}' | b']' if depth == 0 => return None,
+            b'}' | b']' => depth -= 1,
+            b',' if depth == 0 => {
+                current += 1;
+                expecting = true;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Returns the index of the quote closing the JSON string that starts at `start`
+fn string_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut index = start + 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 1,
+            b'"' => return Some(index),
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Finds a top-level key in a TOML document: an assignment before the first table,
+/// a `[field]` table, or the `[[field]]` header of one of its entries
+fn locate_toml(contents: &str, field: &str, entry: Option<usize>) -> Option<usize> {
+    let mut in_root = true;
+    let mut entries = 0;
+    let mut offset = 0;
+    let mut key_line = None;
+
+    for line in contents.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let start = offset + (line.len() - trimmed.len());
+        offset += line.len();
+
+        if let Some(header) = trimmed.strip_prefix("[[") {
+            in_root = false;
+            if header.split(']').next().map(str::trim) == Some(field) {
+                if entry.is_none_or(|entry| entry == entries) {
+                    return Some(start);
+                }
+                entries += 1;
+            }
+        } else if let Some(header) = trimmed.strip_prefix('[') {
+            in_root = false;
+            if header.split(']').next().map(str::trim) == Some(field) {
+                return Some(start);
+            }
+        } else if in_root
+            && key_line.is_none()
+            && trimmed
+                .strip_prefix(field)
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        {
+            key_line = Some(start);
+        }
+    }
+    key_line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{
+    "auto_threshold": 85.0,
+    "suspend": { "rules": 1 },
+    "rules": [
+        { "action": "empty_standby_list" },
+        { "action": "compact_memory" },
+        { "action": "empty_working_sets" }
+    ]
+}"#;
+
+    const TOML: &str = r#"# comment
+auto_threshold = 85.0
+rules_count = 2
+
+[suspend]
+percent = 90.0
+
+[[rules]]
+action = "empty_standby_list"
+
+[[rules]]
+action = "compact_memory"
+
+[[rules]]
+action = "empty_working_sets"
+"#;
+
+    fn source(contents: &str, format: ConfigFormat) -> ConfigSource {
+        ConfigSource {
+            path: PathBuf::from("config"),
+            contents: contents.to_string(),
+            format,
+            dropped: HashMap::new(),
+        }
+    }
+
+    fn located(source: &ConfigSource, field: &str, entry: Option<usize>) -> String {
+        let message = match entry {
+            Some(entry) => ConfigMessage::entry(field, entry, "bad".to_string(), true),
+            None => ConfigMessage::field(field, "bad".to_string(), true),
+        };
+        source.render(message).0
+    }
+
+    #[test]
+    fn locate_json_finds_top_level_keys_only() {
+        assert_eq!(locate_json(JSON, "auto_threshold", None), Some(6));
+        // "rules" inside suspend is nested, the top-level key comes later
+        let rules = locate_json(JSON, "rules", None).unwrap();
+        assert!(JSON[rules..].starts_with("\"rules\": ["));
+        assert_eq!(locate_json(JSON, "missing", None), None);
+    }
+
+    #[test]
+    fn locate_json_finds_entries() {
+        let entry = locate_json(JSON, "rules", Some(1)).unwrap();
+        assert!(JSON[entry..].starts_with("{ \"action\": \"compact_memory\""));
+        // An entry past the end points at the key
+        assert_eq!(
+            locate_json(JSON, "rules", Some(5)),
+            locate_json(JSON, "rules", None)
+        );
+    }
+
+    #[test]
+    fn locate_toml_finds_keys_tables_and_entries() {
+        let key = locate_toml(TOML, "auto_threshold", None).unwrap();
+        assert!(TOML[key..].starts_with("auto_threshold ="));
+        // A key is only matched whole, not as the prefix of another key
+        assert_eq!(locate_toml(TOML, "rules", None), TOML.find("[[rules]]"));
+        let table = locate_toml(TOML, "suspend", None).unwrap();
+        assert!(TOML[table..].starts_with("[suspend]"));
+        let entry = locate_toml(TOML, "rules", Some(2)).unwrap();
+        assert!(TOML[entry..].starts_with("[[rules]]\naction = \"empty_working_sets\""));
+        // Keys of tables aren't top-level keys
+        assert_eq!(locate_toml(TOML, "percent", None), None);
+    }
+
+    #[test]
+    fn render_reports_line_and_column() {
+        let json = source(JSON, ConfigFormat::Json);
+        assert_eq!(
+            located(&json, "auto_threshold", None),
+            "config:2:5: auto_threshold: bad"
+        );
+        assert_eq!(
+            located(&json, "rules", Some(2)),
+            "config:7:9: rules[2]: bad"
+        );
+
+        let toml = source(TOML, ConfigFormat::Toml);
+        assert_eq!(located(&toml, "suspend", None), "config:5:1: suspend: bad");
+        assert_eq!(
+            located(&toml, "rules", Some(1)),
+            "config:11:1: rules[1]: bad"
+        );
+        assert_eq!(located(&toml, "missing", None), "config: missing: bad");
+    }
+
+    #[test]
+    fn render_maps_entries_past_dropped_ones() {
+        let mut toml = source(TOML, ConfigFormat::Toml);
+        toml.dropped.insert("rules".to_string(), vec![0]);
+        // The first remaining entry is the second one in the file
+        assert_eq!(
+            located(&toml, "rules", Some(0)),
+            "config:11:1: rules[1]: bad"
+        );
+        assert_eq!(
+            located(&toml, "rules", Some(1)),
+            "config:14:1: rules[2]: bad"
+        );
+
+        let mut json = source(JSON, ConfigFormat::Json);
+        json.dropped.insert("rules".to_string(), vec![1]);
+        assert_eq!(
+            located(&json, "rules", Some(0)),
+            "config:5:9: rules[0]: bad"
+        );
+        assert_eq!(
+            located(&json, "rules", Some(1)),
+            "config:7:9: rules[2]: bad"
+        );
+    }
+
+    #[test]
+    fn toml_set_keeps_comments_and_layout() {
+        let contents = "# header\nversion = 1 # old\n\n# rules\n[[rules]]\n\
+                        trigger = { type = \"oom_kill\" }\naction = \"a\" # note\n";
+        let mut document = ConfigDocument::parse(ConfigFormat::Toml, contents).unwrap();
+        document.set("version", &Value::from(2)).unwrap();
+        document
+            .set(
+                "rules",
+                &serde_json::json!([{ "trigger": { "type": "oom_kill" }, "action": "b" }]),
+            )
+            .unwrap();
+        document
+            .set("auto_threshold", &Value::from(72.3f32))
+            .unwrap();
+        assert_eq!(
+            document.contents().unwrap(),
+            "# header\nversion = 2 # old\nauto_threshold = 72.3\n\n# rules\n[[rules]]\n\
+             trigger = { type = \"oom_kill\" }\naction = \"b\" # note\n"
+        );
+    }
+}
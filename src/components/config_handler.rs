@@ -1,24 +1,52 @@
-use serde_json;
+use glob::Pattern;
+use serde_json::{self, Map, Value};
+use sysinfo::Signal;
 
-use std::{
-    fs,
-    io::{self, Error, ErrorKind},
-    path::Path,
-};
+use std::{collections::HashMap, fs, io, mem, path::Path};
 
 use crate::components::{
-    constants::{CONFIG_FILE, DEFAULT_AUTO_THRESHOLD},
-    structs::{Config, RamMonitor},
+    config_file::{ConfigDocument, ConfigFormat, ConfigMessage, ConfigSource, render_unlocated},
+    constants::{
+        CONFIG_VERSION, DEFAULT_AUTO_THRESHOLD, DEFAULT_PROFILE, RESERVED_HOTKEYS,
+        TOML_CONFIG_VERSION,
+    },
+    custom_actions::{config_is_world_writable, validate_custom_action},
+    eviction::EvictionFilter,
+    macros::validate_macro,
+    memory_management::{Action, ActionId, Commands},
+    migration::{config_version, migrate, migrated_fields},
+    process_rules::parse_signal,
+    structs::{
+        AutoRule, Config, CustomAction, OomKillerConfig, ProcessAction, ProcessRule, Profile,
+        RamMonitor, RuleTrigger, SuspendConfig,
+    },
 };
 
 type ConfigResult<T> = io::Result<T>;
-type ValidationMessage = (String, bool);
+pub type ValidationMessage = (String, bool);
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            version: CONFIG_VERSION,
             auto_threshold: DEFAULT_AUTO_THRESHOLD,
-            auto_action: String::from("Empty Working Sets"),
+            auto_action: ActionId::RamMap(Commands::EmptyWorkingSets),
+            forecast_lead_secs: None,
+            cgroup_root: None,
+            rules: Vec::new(),
+            page_cache_dirs: Vec::new(),
+            eviction_targets: Vec::new(),
+            prewarm_paths: Vec::new(),
+            prewarm_lock_bytes: None,
+            oom_killer: None,
+            suspend: None,
+            notify_socket: None,
+            process_rules: Vec::new(),
+            custom_actions: Vec::new(),
+            macros: Vec::new(),
+            profiles: Vec::new(),
+            active_profile: None,
+            unknown: Map::new(),
         }
     }
 }
@@ -29,16 +57,62 @@ impl Config {
         (20.0..=95.0).contains(&threshold)
     }
 
-    /// Validates if the given action is one of the supported memory management actions
-    fn is_valid_action(action: &str) -> bool {
-        matches!(
-            action,
-            "Empty Working Sets"
-                | "Empty System Working Sets"
-                | "Empty Modified Page Lists"
-                | "Empty Standby List"
-                | "Empty Priority 0 Standby List"
-        )
+    /// Validates that the given action exists and can be auto-executed:
+    /// a RAMMap command, custom action or macro
+    fn check_auto_action(action: &ActionId, config: &Config) -> Result<(), String> {
+        match Action::from_id(action, config)? {
+            action if action.is_auto_action() => Ok(()),
+            action => Err(format!(
+                "{} can't be auto-executed",
+                action.display_name(config)
+            )),
+        }
+    }
+
+    /// Validates that a rule trigger's parameters are within acceptable ranges
+    fn is_valid_trigger(trigger: &RuleTrigger) -> bool {
+        match trigger {
+            RuleTrigger::OomKill => true,
+            RuleTrigger::NodeThreshold { percent, .. } | RuleTrigger::Fragmentation { percent } => {
+                *percent > 0.0 && *percent <= 100.0
+            }
+        }
+    }
+
+    /// Validates that the killer's critical levels are percentages, with available memory
+    /// kept low enough that the killer stays a last resort
+    fn is_valid_killer(killer: &OomKillerConfig) -> bool {
+        (killer.mem_available_percent > 0.0 && killer.mem_available_percent <= 50.0)
+            && killer
+                .swap_free_percent
+                .is_none_or(|percent| (0.0..=100.0).contains(&percent))
+    }
+
+    /// Validates that suspension has processes to act on and resumes below its trigger level
+    fn is_valid_suspend(suspend: &SuspendConfig) -> bool {
+        !suspend.processes.is_empty()
+            && Self::is_valid_threshold(suspend.percent)
+            && suspend.recovery_percent > 0.0
+            && suspend.recovery_percent < suspend.percent
+    }
+
+    /// Validates that a process rule matches something, has a threshold and a runnable action
+    fn is_valid_process_rule(rule: &ProcessRule) -> bool {
+        let action_valid = match &rule.action {
+            // A process stopped by a rule would never be resumed, `suspend` handles stopping
+            ProcessAction::Signal { signal } => {
+                parse_signal(signal).is_some_and(|signal| signal != Signal::Stop)
+            }
+            ProcessAction::Command { command } => !command.trim().is_empty(),
+            ProcessAction::Trim => true,
+        };
+        rule.matcher.is_set()
+            && rule
+                .matcher
+                .patterns()
+                .all(|pattern| Pattern::new(pattern).is_ok())
+            && (rule.rss_bytes.is_some() || rule.swap_bytes.is_some())
+            && action_valid
     }
 
     /// Validates configuration values and returns a vector of validation messages
@@ -46,22 +120,25 @@ impl Config {
     ///
     /// # Arguments
     /// * `config` - Mutable reference to the configuration to validate
+    /// * `path` - The config file, checked for permissions before custom actions and
+    ///   command process rules are allowed
     ///
     /// # Returns
-    /// Vector of tuples containing (message, is_error)
-    fn validate_config(config: &mut Config) -> Vec<ValidationMessage> {
+    /// Vector of messages, naming the field each one concerns
+    fn validate_config(config: &mut Config, path: &Path) -> Vec<ConfigMessage> {
         let mut messages = Vec::new();
 
         if !Self::is_valid_threshold(config.auto_threshold) {
             messages.extend([
-                (
+                ConfigMessage::field(
+                    "auto_threshold",
                     format!(
                         "Invalid threshold value {}, using default",
                         config.auto_threshold
                     ),
                     true,
                 ),
-                (
+                ConfigMessage::new(
                     format!("Using default threshold: {DEFAULT_AUTO_THRESHOLD}%"),
                     false,
                 ),
@@ -69,68 +146,822 @@ impl Config {
             config.auto_threshold = DEFAULT_AUTO_THRESHOLD;
         }
 
-        if !Self::is_valid_action(&config.auto_action) {
+        if config.forecast_lead_secs == Some(0) {
+            messages.push(ConfigMessage::field(
+                "forecast_lead_secs",
+                "Invalid forecast lead time 0s, disabling pre-emptive execution".to_string(),
+                true,
+            ));
+            config.forecast_lead_secs = None;
+        }
+
+        if let Some(killer) = &config.oom_killer
+            && !Self::is_valid_killer(killer)
+        {
+            messages.push(ConfigMessage::field(
+                "oom_killer",
+                format!(
+                    "Invalid oom_killer levels ({}% available), killer disabled",
+                    killer.mem_available_percent
+                ),
+                true,
+            ));
+            config.oom_killer = None;
+        }
+
+        if let Some(suspend) = &config.suspend
+            && !Self::is_valid_suspend(suspend)
+        {
+            messages.push(ConfigMessage::field(
+                "suspend",
+                format!(
+                    "Invalid suspend levels ({}% / {}% recovery), suspension disabled",
+                    suspend.percent, suspend.recovery_percent
+                ),
+                true,
+            ));
+            config.suspend = None;
+        }
+
+        let builtin = Config::default();
+        let mut names: Vec<String> = Action::all(&builtin)
+            .iter()
+            .chain([&Action::Prewarm])
+            .map(|action| action.display_name(&builtin).to_string())
+            .collect();
+        let mut valid_targets = Vec::new();
+        for (index, target) in mem::take(&mut config.eviction_targets)
+            .into_iter()
+            .enumerate()
+        {
+            let problem = if target.paths.is_empty() {
+                Some("no paths".to_string())
+            } else if names.contains(&target.name) {
+                Some("a duplicate name".to_string())
+            } else {
+                EvictionFilter::from_target(&target)
+                    .err()
+                    .map(|e| format!("pattern error: {e}"))
+            };
+            match problem {
+                Some(problem) => messages.push(ConfigMessage::entry(
+                    "eviction_targets",
+                    index,
+                    format!(
+                        "Invalid eviction target {} ({problem}), target disabled",
+                        target.name
+                    ),
+                    true,
+                )),
+                None => {
+                    names.push(target.name.clone());
+                    valid_targets.push(target);
+                }
+            }
+        }
+        config.eviction_targets = valid_targets;
+
+        let hotkeys: Vec<char> = RESERVED_HOTKEYS.chars().collect();
+        let world_writable = config_is_world_writable(path);
+        let mut valid_actions: Vec<CustomAction> = Vec::new();
+        for (index, action) in mem::take(&mut config.custom_actions)
+            .into_iter()
+            .enumerate()
+        {
+            let taken_hotkeys: Vec<char> = valid_actions
+                .iter()
+                .filter_map(|action| action.hotkey)
+                .chain(hotkeys.iter().copied())
+                .collect();
+            let problem = if world_writable {
+                Err("the config file is writable by other users".to_string())
+            } else {
+                validate_custom_action(&action, &names, &taken_hotkeys)
+            };
+            match problem {
+                Err(problem) => messages.push(ConfigMessage::entry(
+                    "custom_actions",
+                    index,
+                    format!(
+                        "Unsafe custom action {} ({problem}), action disabled",
+                        action.name
+                    ),
+                    true,
+                )),
+                Ok(()) => {
+                    names.push(action.name.clone());
+                    valid_actions.push(action);
+                }
+            }
+        }
+        config.custom_actions = valid_actions;
+
+        let mut hotkeys: Vec<char> = hotkeys
+            .into_iter()
+            .chain(
+                config
+                    .custom_actions
+                    .iter()
+                    .filter_map(|action| action.hotkey),
+            )
+            .collect();
+        let mut valid_macros = Vec::new();
+        for (index, action_macro) in mem::take(&mut config.macros).into_iter().enumerate() {
+            match validate_macro(&action_macro, &names, &hotkeys, config) {
+                Err(problem) => messages.push(ConfigMessage::entry(
+                    "macros",
+                    index,
+                    format!(
+                        "Invalid macro {} ({problem}), macro disabled",
+                        action_macro.name
+                    ),
+                    true,
+                )),
+                Ok(()) => {
+                    names.push(action_macro.name.clone());
+                    hotkeys.extend(action_macro.hotkey);
+                    valid_macros.push(action_macro);
+                }
+            }
+        }
+        config.macros = valid_macros;
+
+        if let Err(problem) = Self::check_auto_action(&config.auto_action, config) {
             messages.extend([
-                (
-                    format!("Invalid action {}, using default", config.auto_action),
+                ConfigMessage::field(
+                    "auto_action",
+                    format!("Invalid auto_action: {problem}, using default"),
                     true,
                 ),
-                (
+                ConfigMessage::new(
                     "Using default action: Empty Working Sets".to_string(),
                     false,
                 ),
             ]);
-            config.auto_action = String::from("Empty Working Sets");
+            config.auto_action = ActionId::RamMap(Commands::EmptyWorkingSets);
         }
 
+        let (valid_rules, problems) = Self::validate_rules(mem::take(&mut config.rules), config);
+        config.rules = valid_rules;
+        messages.extend(
+            problems
+                .into_iter()
+                .map(|(index, problem)| ConfigMessage::entry("rules", index, problem, true)),
+        );
+
+        let (valid_rules, problems) =
+            Self::validate_process_rules(mem::take(&mut config.process_rules), world_writable);
+        config.process_rules = valid_rules;
+        messages.extend(
+            problems.into_iter().map(|(index, problem)| {
+                ConfigMessage::entry("process_rules", index, problem, true)
+            }),
+        );
+
+        messages.extend(Self::validate_profiles(config, world_writable));
+
         messages
     }
 
-    /// Loads configuration from file, falling back to defaults if necessary
+    /// Drops the rules that can't run
     ///
     /// # Arguments
-    /// * `ram_monitor` - Mutable reference to RamMonitor for logging
+    /// * `rules` - The rules to check
+    /// * `config` - Configuration providing the actions rules can refer to
     ///
     /// # Returns
-    /// A Config instance, either from file or defaults
-    pub fn load(ram_monitor: &mut RamMonitor) -> Self {
-        if !Path::new(CONFIG_FILE).exists() {
-            return Config::default();
+    /// The valid rules, and the index and problem of each dropped one
+    fn validate_rules(
+        rules: Vec<AutoRule>,
+        config: &Config,
+    ) -> (Vec<AutoRule>, Vec<(usize, String)>) {
+        let mut valid_rules = Vec::new();
+        let mut problems = Vec::new();
+        for (index, rule) in rules.into_iter().enumerate() {
+            let problem = if Self::is_valid_trigger(&rule.trigger) {
+                Action::from_id(&rule.action, config).err()
+            } else {
+                Some("trigger out of range".to_string())
+            };
+            match problem {
+                Some(problem) => problems.push((
+                    index,
+                    format!(
+                        "Invalid {} rule ({problem}), rule disabled",
+                        rule.trigger.describe()
+                    ),
+                )),
+                None => valid_rules.push(rule),
+            }
+        }
+        (valid_rules, problems)
+    }
+
+    /// Drops the process rules that can't run
+    ///
+    /// # Arguments
+    /// * `rules` - The rules to check
+    /// * `world_writable` - Whether the config file is writable by other users, which
+    ///   disables rules that run commands, like custom actions
+    ///
+    /// # Returns
+    /// The valid rules, and the index and problem of each dropped one
+    fn validate_process_rules(
+        rules: Vec<ProcessRule>,
+        world_writable: bool,
+    ) -> (Vec<ProcessRule>, Vec<(usize, String)>) {
+        let mut valid_rules = Vec::new();
+        let mut problems = Vec::new();
+        for (index, rule) in rules.into_iter().enumerate() {
+            let problem = if !Self::is_valid_process_rule(&rule) {
+                format!(
+                    "Invalid process rule {} ({}), rule disabled",
+                    rule.describe(),
+                    rule.action.describe()
+                )
+            } else if world_writable && matches!(rule.action, ProcessAction::Command { .. }) {
+                format!(
+                    "Unsafe process rule {} (the config file is writable by other users), rule disabled",
+                    rule.describe()
+                )
+            } else {
+                valid_rules.push(rule);
+                continue;
+            };
+            problems.push((index, problem));
         }
+        (valid_rules, problems)
+    }
 
-        fs::read_to_string(CONFIG_FILE)
-            .map_err(|e| {
-                ram_monitor.add_log(
-                    format!("Error reading config file: {e}, using defaults"),
+    /// Validates the profiles' settings the same way as the base settings they replace
+    /// Invalid settings are removed from the profile, so the base setting applies instead
+    ///
+    /// # Arguments
+    /// * `config` - Configuration holding the profiles
+    /// * `world_writable` - Whether the config file is writable by other users
+    ///
+    /// # Returns
+    /// Vector of messages, naming the profile each one concerns
+    fn validate_profiles(config: &mut Config, world_writable: bool) -> Vec<ConfigMessage> {
+        let mut messages = Vec::new();
+        let mut names: Vec<String> = vec![DEFAULT_PROFILE.to_string()];
+        let mut valid_profiles: Vec<Profile> = Vec::new();
+
+        for (index, mut profile) in mem::take(&mut config.profiles).into_iter().enumerate() {
+            let name = profile.name.clone();
+            if name.trim().is_empty() || names.contains(&name) {
+                let problem = if name.trim().is_empty() {
+                    "no name"
+                } else {
+                    "a duplicate or reserved name"
+                };
+                messages.push(ConfigMessage::entry(
+                    "profiles",
+                    index,
+                    format!("Invalid profile {name} ({problem}), profile disabled"),
                     true,
-                );
-                e
-            })
-            .and_then(|contents| {
-                serde_json::from_str(&contents).map_err(|e| {
-                    ram_monitor.add_log(
-                        format!("Error parsing config file: {e}, using defaults"),
+                ));
+                continue;
+            }
+            let mut problem = |text: String| {
+                messages.push(ConfigMessage::entry(
+                    "profiles",
+                    index,
+                    format!("Profile {name}: {text}"),
+                    true,
+                ));
+            };
+
+            if let Some(threshold) = profile.auto_threshold
+                && !Self::is_valid_threshold(threshold)
+            {
+                problem(format!(
+                    "Invalid threshold value {threshold}, using auto_threshold"
+                ));
+                profile.auto_threshold = None;
+            }
+            if let Some(action) = &profile.auto_action
+                && let Err(e) = Self::check_auto_action(action, config)
+            {
+                problem(format!("Invalid auto_action: {e}, using auto_action"));
+                profile.auto_action = None;
+            }
+            if let Some(rules) = profile.rules.take() {
+                let (valid_rules, problems) = Self::validate_rules(rules, config);
+                for (rule, text) in problems {
+                    problem(format!("rules[{rule}]: {text}"));
+                }
+                profile.rules = Some(valid_rules);
+            }
+            if let Some(rules) = profile.process_rules.take() {
+                let (valid_rules, problems) = Self::validate_process_rules(rules, world_writable);
+                for (rule, text) in problems {
+                    problem(format!("process_rules[{rule}]: {text}"));
+                }
+                profile.process_rules = Some(valid_rules);
+            }
+
+            names.push(name);
+            valid_profiles.push(profile);
+        }
+        config.profiles = valid_profiles;
+
+        if let Some(active) = &config.active_profile
+            && config.profile_index(active).is_none()
+        {
+            messages.push(ConfigMessage::field(
+                "active_profile",
+                format!("Unknown profile {active}, using the default profile"),
+                true,
+            ));
+            config.active_profile = None;
+        }
+
+        messages
+    }
+
+    /// Reads and validates a configuration file in the format given by its extension
+    ///
+    /// # Arguments
+    /// * `path` - The config file to read
+    /// * `upgrade` - Whether a config written for an older schema version is upgraded on disk
+    ///
+    /// # Returns
+    /// A Config instance, either from file or defaults, and the messages to report,
+    /// located at the offending line and column where possible
+    pub fn read_from(path: &Path, upgrade: bool) -> (Self, Vec<ValidationMessage>) {
+        if !path.exists() {
+            return (Config::default(), Vec::new());
+        }
+
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => {
+                return (
+                    Config::default(),
+                    vec![(
+                        format!(
+                            "Error reading config file {}: {e}, using defaults",
+                            path.display()
+                        ),
                         true,
-                    );
-                    Error::new(ErrorKind::InvalidData, e)
-                })
-            })
-            .map(|mut config| {
-                let messages = Self::validate_config(&mut config);
-                for msg in messages {
-                    ram_monitor.add_log(msg.0, msg.1);
+                    )],
+                );
+            }
+        };
+        let mut source = ConfigSource {
+            path: path.to_path_buf(),
+            contents,
+            format: ConfigFormat::from_path(path),
+            dropped: HashMap::new(),
+        };
+
+        // The file is left as it is, as saving refuses to overwrite a file that doesn't parse
+        let mut file = match Self::parse_fields(source.format, &source.contents) {
+            Ok(file) => file,
+            Err((error, offset)) => {
+                let location = offset.map_or_else(
+                    || source.path.display().to_string(),
+                    |offset| source.position(offset),
+                );
+                let message = format!(
+                    "Error parsing config file {location}: {error}, \
+                     using defaults without saving changes until it is fixed"
+                );
+                return (Config::default(), vec![(message, true)]);
+            }
+        };
+
+        let mut messages = Vec::new();
+        let version = config_version(&file);
+        if version > CONFIG_VERSION {
+            messages.push(ConfigMessage::field(
+                "version",
+                format!(
+                    "Config version {version} is newer than supported ({CONFIG_VERSION}), \
+                     unknown fields are kept"
+                ),
+                true,
+            ));
+        }
+        let original = file.clone();
+        let mut notes = Vec::new();
+        // A file the migrations leave as it was, apart from its version, isn't rewritten
+        let upgraded = migrate(&mut file, &mut notes)
+            .map(|from| (from, migrated_fields(&original, &file)))
+            .filter(|(_, changed)| !changed.is_empty());
+        messages.extend(notes);
+        if let Some((from, changed)) = upgraded {
+            if upgrade {
+                messages.push(ConfigMessage::new(
+                    Self::backup(path, &format!("v{from}")).0,
+                    false,
+                ));
+                messages.push(match Self::write_upgraded(&source, &file, &changed) {
+                    Ok(()) => ConfigMessage::new(
+                        format!("Upgraded config from version {from} to {CONFIG_VERSION}"),
+                        false,
+                    ),
+                    Err(e) => {
+                        ConfigMessage::new(format!("Failed to write upgraded config: {e}"), true)
+                    }
+                });
+            } else {
+                messages.push(ConfigMessage::new(
+                    format!("Config version {from} needs to be upgraded to {CONFIG_VERSION}"),
+                    false,
+                ));
+            }
+        }
+
+        // Entries dropped while parsing are reported with their index in the file,
+        // validation messages with their index among the remaining entries
+        let mut dropped = HashMap::new();
+        let (mut config, parse_messages) = Self::parse_tolerant(file, &mut dropped);
+        messages.extend(parse_messages);
+        let mut rendered: Vec<ValidationMessage> = messages
+            .into_iter()
+            .map(|message| source.render(message))
+            .collect();
+        source.dropped = dropped;
+        rendered.extend(
+            Self::validate_config(&mut config, path)
+                .into_iter()
+                .map(|message| source.render(message)),
+        );
+        (config, rendered)
+    }
+
+    /// Parses a config file's top-level table
+    /// A TOML file without a version was written for the version TOML support was added in
+    fn parse_fields(
+        format: ConfigFormat,
+        contents: &str,
+    ) -> Result<Map<String, Value>, (String, Option<usize>)> {
+        let mut file = format.parse(contents)?;
+        if format == ConfigFormat::Toml && !file.contains_key("version") {
+            file.insert("version".to_string(), Value::from(TOML_CONFIG_VERSION));
+        }
+        Ok(file)
+    }
+
+    /// Builds a config from the file's fields, keeping every valid field when others are invalid
+    /// Invalid fields fall back to their defaults and invalid entries of list fields are dropped
+    ///
+    /// # Arguments
+    /// * `file` - The config file's top-level table, migrated to the current version
+    /// * `dropped` - Receives the indices of dropped list entries per field
+    ///
+    /// # Returns
+    /// The config and a message for every field that could not be used
+    fn parse_tolerant(
+        file: Map<String, Value>,
+        dropped: &mut HashMap<String, Vec<usize>>,
+    ) -> (Self, Vec<ConfigMessage>) {
+        let defaults = match serde_json::to_value(Config::default()) {
+            Ok(Value::Object(defaults)) => defaults,
+            _ => Map::new(),
+        };
+        let mut accepted = defaults.clone();
+        let mut messages = Vec::new();
+
+        for (key, value) in file {
+            if !defaults.contains_key(&key) {
+                messages.push(ConfigMessage::field(
+                    &key,
+                    "Unknown config field, kept as is".to_string(),
+                    false,
+                ));
+                accepted.insert(key, value);
+                continue;
+            }
+
+            let error = match Self::accepts(&accepted, &key, &value) {
+                Ok(()) => {
+                    accepted.insert(key, value);
+                    continue;
                 }
-                config
-            })
-            .unwrap_or_else(|_| Config::default())
+                Err(e) => e,
+            };
+            let Value::Array(entries) = value else {
+                messages.push(ConfigMessage::field(
+                    &key,
+                    format!("Invalid value: {error}, using default"),
+                    true,
+                ));
+                continue;
+            };
+
+            let mut valid = Vec::new();
+            for (index, entry) in entries.into_iter().enumerate() {
+                let single = Value::Array(vec![entry]);
+                match Self::accepts(&accepted, &key, &single) {
+                    Ok(()) => valid.extend(single.as_array().into_iter().flatten().cloned()),
+                    Err(e) => {
+                        messages.push(ConfigMessage::entry(
+                            &key,
+                            index,
+                            format!("Invalid entry: {e}, entry dropped"),
+                            true,
+                        ));
+                        dropped.entry(key.clone()).or_default().push(index);
+                    }
+                }
+            }
+            accepted.insert(key, Value::Array(valid));
+        }
+
+        match serde_json::from_value(Value::Object(accepted)) {
+            Ok(config) => (config, messages),
+            Err(e) => {
+                messages.push(ConfigMessage::new(
+                    format!("Error parsing config file: {e}, using defaults"),
+                    true,
+                ));
+                (Config::default(), messages)
+            }
+        }
+    }
+
+    /// Checks whether a config with the given field set to a value deserializes
+    fn accepts(base: &Map<String, Value>, key: &str, value: &Value) -> serde_json::Result<()> {
+        let mut candidate = base.clone();
+        candidate.insert(key.to_string(), value.clone());
+        serde_json::from_value::<Config>(Value::Object(candidate)).map(|_| ())
+    }
+
+    /// Writes the fields changed by a migration back into the file, leaving the rest as it was
+    ///
+    /// # Arguments
+    /// * `source` - The file as it was read
+    /// * `file` - The file's fields after the migration
+    /// * `changed` - The fields the migration changed
+    fn write_upgraded(
+        source: &ConfigSource,
+        file: &Map<String, Value>,
+        changed: &[String],
+    ) -> io::Result<()> {
+        let mut document = ConfigDocument::parse(source.format, &source.contents)?;
+        for key in changed.iter().map(String::as_str).chain(["version"]) {
+            document.set(key, file.get(key).unwrap_or(&Value::Null))?;
+        }
+        fs::write(&source.path, document.contents()?)
+    }
+
+    /// Copies the config file next to itself before it is rewritten
+    ///
+    /// # Arguments
+    /// * `path` - The config file
+    /// * `label` - Describes the copy, e.g. the version it was written by
+    ///
+    /// # Returns
+    /// A message reporting where the copy was saved, or why it failed
+    fn backup(path: &Path, label: &str) -> ValidationMessage {
+        let backup = format!("{}.{label}.bak", path.display());
+        match fs::copy(path, &backup) {
+            Ok(_) => (format!("Saved a copy of the config to {backup}"), false),
+            Err(e) => (format!("Failed to back up config to {backup}: {e}"), true),
+        }
     }
 
-    /// Saves current configuration to file after validation
-    pub fn save_to_disk(&self) -> ConfigResult<Vec<ValidationMessage>> {
+    /// Loads configuration from file, falling back to defaults if necessary
+    ///
+    /// # Arguments
+    /// * `ram_monitor` - Mutable reference to RamMonitor for logging
+    ///
+    /// # Returns
+    /// A Config instance, either from file or defaults
+    pub fn load(ram_monitor: &mut RamMonitor) -> Self {
+        let path = ram_monitor.paths.config.clone();
+        let (config, messages) = Self::read_from(&path, true);
+        for (msg, is_error) in messages {
+            ram_monitor.add_log(msg, is_error);
+        }
+        config
+    }
+
+    /// Writes the complete configuration to a new config file after validation
+    /// The file's directory is created if the config is saved for the first time
+    ///
+    /// # Arguments
+    /// * `path` - The config file to write, in the format given by its extension
+    pub fn save_to_disk(&self, path: &Path) -> ConfigResult<Vec<ValidationMessage>> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
         let mut config = self.clone();
-        let messages = Self::validate_config(&mut config);
-        let config_json = serde_json::to_string_pretty(&config)?;
-        fs::write(CONFIG_FILE, config_json)?;
-        Ok(messages)
+        config.version = config.version.max(CONFIG_VERSION);
+        let messages = Self::validate_config(&mut config, path);
+        let contents = ConfigFormat::from_path(path).serialize(&config)?;
+        fs::write(path, contents)?;
+        Ok(render_unlocated(messages))
+    }
+
+    /// Sets one top-level field in the config file, leaving the rest of the file as it is
+    ///
+    /// # Arguments
+    /// * `path` - The config file, which must exist and parse
+    /// * `key` - The field to set
+    /// * `value` - Its new value, or null to remove it
+    pub fn save_field(path: &Path, key: &str, value: &Value) -> ConfigResult<()> {
+        Self::edit_file(path, |document| document.set(key, value))
+    }
+
+    /// Sets one field of a named list entry in the config file, such as a profile's threshold,
+    /// leaving the rest of the file as it is
+    ///
+    /// # Arguments
+    /// * `path` - The config file, which must exist and parse
+    /// * `list` - The list the entry is in
+    /// * `name` - The entry's name
+    /// * `key` - The entry's field to set
+    /// * `value` - Its new value, or null to remove it
+    pub fn save_entry_field(
+        path: &Path,
+        list: &str,
+        name: &str,
+        key: &str,
+        value: &Value,
+    ) -> ConfigResult<()> {
+        Self::edit_file(path, |document| document.set_entry(list, name, key, value))
+    }
+
+    /// Applies an edit to the config file's own document and writes it back
+    fn edit_file(
+        path: &Path,
+        edit: impl FnOnce(&mut ConfigDocument) -> io::Result<()>,
+    ) -> ConfigResult<()> {
+        let contents = fs::read_to_string(path)?;
+        let edited = Self::edit_contents(path, &contents, edit)?;
+        fs::write(path, edited)
+    }
+
+    /// Applies an edit to a config file's contents
+    /// A file that doesn't parse is never overwritten, as the edit would replace it
+    ///
+    /// # Returns
+    /// The contents to write back
+    fn edit_contents(
+        path: &Path,
+        contents: &str,
+        edit: impl FnOnce(&mut ConfigDocument) -> io::Result<()>,
+    ) -> ConfigResult<String> {
+        let mut document =
+            ConfigDocument::parse(ConfigFormat::from_path(path), contents).map_err(|e| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} has errors and was left unchanged: {e}", path.display()),
+                )
+            })?;
+        edit(&mut document)?;
+        document.contents()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(value: Value) -> (Config, Vec<ConfigMessage>, HashMap<String, Vec<usize>>) {
+        let Value::Object(file) = value else {
+            panic!("not an object");
+        };
+        let mut dropped = HashMap::new();
+        let (config, messages) = Config::parse_tolerant(file, &mut dropped);
+        (config, messages, dropped)
+    }
+
+    #[test]
+    fn parse_tolerant_keeps_valid_fields() {
+        let (config, messages, dropped) = parse(json!({
+            "auto_threshold": "high",
+            "auto_action": "empty_standby_list",
+            "page_cache_dirs": ["/var/cache"]
+        }));
+        assert_eq!(config.auto_threshold, DEFAULT_AUTO_THRESHOLD);
+        assert!(config.auto_action == ActionId::RamMap(Commands::EmptyStandbyList));
+        assert_eq!(config.page_cache_dirs, vec!["/var/cache".to_string()]);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].field.as_deref(), Some("auto_threshold"));
+        assert!(messages[0].is_error);
+        assert!(dropped.is_empty());
+    }
+
+    #[test]
+    fn parse_tolerant_drops_invalid_entries() {
+        let (config, messages, dropped) = parse(json!({
+            "auto_threshold": 80.0,
+            "auto_action": "empty_standby_list",
+            "rules": [
+                { "trigger": { "type": "bogus" }, "action": "empty_standby_list" },
+                { "trigger": { "type": "oom_kill" }, "action": "compact_memory" },
+                { "action": "empty_working_sets" }
+            ]
+        }));
+        assert_eq!(config.rules.len(), 1);
+        assert_eq!(dropped.get("rules"), Some(&vec![0, 2]));
+        let entries: Vec<_> = messages.iter().map(|message| message.entry).collect();
+        assert_eq!(entries, vec![Some(0), Some(2)]);
+    }
+
+    #[test]
+    fn parse_tolerant_keeps_unknown_fields() {
+        let (config, messages, _) = parse(json!({
+            "auto_threshold": 80.0,
+            "auto_action": "empty_standby_list",
+            "from_the_future": { "enabled": true }
+        }));
+        assert_eq!(
+            config.unknown.get("from_the_future"),
+            Some(&json!({ "enabled": true }))
+        );
+        assert_eq!(messages.len(), 1);
+        assert!(!messages[0].is_error);
+    }
+
+    #[test]
+    fn command_rules_need_a_private_config() {
+        let rules: Vec<ProcessRule> = serde_json::from_value(json!([
+            { "match": { "name": "a" }, "rss_bytes": 1, "action": { "type": "command", "command": "true" } },
+            { "match": { "name": "b" }, "rss_bytes": 1, "action": { "type": "trim" } }
+        ]))
+        .unwrap();
+
+        let (valid, problems) = Config::validate_process_rules(rules.clone(), false);
+        assert_eq!(valid.len(), 2);
+        assert!(problems.is_empty());
+
+        let (valid, problems) = Config::validate_process_rules(rules, true);
+        assert_eq!(valid.len(), 1);
+        assert!(matches!(valid[0].action, ProcessAction::Trim));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].0, 0);
+    }
+
+    #[test]
+    fn saving_a_field_keeps_edits_that_were_not_reloaded() {
+        // The file gained a rule after the observer loaded it
+        let contents = "# tuned\nauto_threshold = 80.0 # note\nauto_action = \"empty_standby_list\"\n\n\
+                        [[rules]]\ntrigger = { type = \"oom_kill\" }\naction = \"compact_memory\"\n";
+        let path = Path::new("test_config.toml");
+        let saved = Config::edit_contents(path, contents, |document| {
+            document.set("auto_threshold", &json!(85.0))
+        })
+        .unwrap();
+        assert_eq!(saved, contents.replace("80.0", "85.0"));
+
+        let saved = Config::edit_contents(path, contents, |document| {
+            document.set("active_profile", &json!("gaming"))
+        })
+        .unwrap();
+        assert!(
+            saved.contains("auto_action = \"empty_standby_list\"\nactive_profile = \"gaming\"\n")
+        );
+        assert!(saved.contains("[[rules]]"));
+    }
+
+    #[test]
+    fn saving_a_profile_field_only_changes_that_profile() {
+        let contents = "[[profiles]]\nname = \"gaming\"\nauto_threshold = 70.0 # low\n\n\
+                        [[profiles]]\nname = \"compiling\"\nauto_threshold = 90.0\n";
+        let path = Path::new("test_config.toml");
+        let saved = Config::edit_contents(path, contents, |document| {
+            document.set_entry("profiles", "compiling", "auto_threshold", &json!(95.0))
+        })
+        .unwrap();
+        assert_eq!(saved, contents.replace("90.0", "95.0"));
+
+        let json_path = Path::new("test_config.json");
+        let json = "{\"profiles\": [{\"name\": \"gaming\"}, {\"name\": \"compiling\"}]}";
+        let saved = Config::edit_contents(json_path, json, |document| {
+            document.set_entry(
+                "profiles",
+                "gaming",
+                "auto_action",
+                &json!("compact_memory"),
+            )
+        })
+        .unwrap();
+        let saved: Value = serde_json::from_str(&saved).unwrap();
+        assert_eq!(
+            saved["profiles"],
+            json!([{ "name": "gaming", "auto_action": "compact_memory" }, { "name": "compiling" }])
+        );
+
+        let missing = Config::edit_contents(path, contents, |document| {
+            document.set_entry("profiles", "unknown", "auto_threshold", &json!(95.0))
+        });
+        assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn saving_refuses_to_replace_a_broken_file() {
+        let path = Path::new("test_config.json");
+        let error = Config::edit_contents(path, "{ \"auto_threshold\": ", |document| {
+            document.set("auto_threshold", &json!(85.0))
+        })
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
     }
 }
@@ -0,0 +1,172 @@
+use serde_json::Value;
+use std::time::Instant;
+
+use crate::components::{
+    constants::CONFIG_RELOAD_CHECK_MS,
+    memory_management::Action,
+    structs::{Config, RamMonitor},
+    utils::is_due,
+};
+
+/// Describes a config value in a change summary
+/// Lists and tables are summarized, as they don't fit on a log line
+fn describe_value(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::from("unset"),
+        Some(Value::Array(entries)) if entries.len() == 1 => String::from("1 entry"),
+        Some(Value::Array(entries)) => format!("{} entries", entries.len()),
+        Some(Value::Object(_)) => String::from("table"),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// Lists the top-level fields that differ between two configs
+///
+/// # Arguments
+/// * `old` - The config in use
+/// * `new` - The config that replaces it
+///
+/// # Returns
+/// * The name of each changed field with a `field: old -> new` line describing the change,
+///   in the new config's field order
+pub fn config_diff(old: &Config, new: &Config) -> Vec<(String, String)> {
+    let (Ok(Value::Object(old)), Ok(Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return Vec::new();
+    };
+
+    let removed = old.keys().filter(|key| !new.contains_key(*key));
+    new.keys()
+        .chain(removed)
+        .filter(|key| old.get(*key) != new.get(*key))
+        .map(|key| {
+            let (before, after) = (old.get(key), new.get(key));
+            let line = match (before, after) {
+                (Some(Value::Array(_)), Some(Value::Array(_)))
+                | (Some(Value::Object(_)), Some(Value::Object(_)))
+                    if describe_value(before) == describe_value(after) =>
+                {
+                    format!("{key}: changed")
+                }
+                _ => format!(
+                    "{key}: {} -> {}",
+                    describe_value(before),
+                    describe_value(after)
+                ),
+            };
+            (key.clone(), line)
+        })
+        .collect()
+}
+
+impl RamMonitor {
+    /// Reloads the config when the file's modification time changes
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    pub fn check_config_reload(&mut self) {
+        if !is_due(self.last_config_check, CONFIG_RELOAD_CHECK_MS) {
+            return;
+        }
+        self.last_config_check = Some(Instant::now());
+
+        let modified = self.paths.config_modified();
+        if modified == self.config_modified {
+            return;
+        }
+        self.config_modified = modified;
+
+        if modified.is_none() {
+            self.add_log(
+                format!(
+                    "Config file {} was removed, keeping current settings",
+                    self.paths.config.display()
+                ),
+                true,
+            );
+            return;
+        }
+        self.reload_config();
+    }
+
+    /// Reads and validates the config file, then applies it in place of the current config
+    /// A file with errors is rejected as a whole, so the previous good config stays active
+    pub fn reload_config(&mut self) {
+        let (config, messages) = Config::read_from(&self.paths.config, false);
+        if messages.iter().any(|(_, is_error)| *is_error) {
+            for (msg, is_error) in messages {
+                self.add_log(msg, is_error);
+            }
+            self.add_log(
+                "Config reload failed, keeping previous settings".to_string(),
+                true,
+            );
+            return;
+        }
+        for (msg, is_error) in messages {
+            self.add_log(msg, is_error);
+        }
+
+        let changes = config_diff(&self.config, &config);
+        if changes.is_empty() {
+            self.add_log("Config reloaded, no settings changed".to_string(), false);
+            return;
+        }
+
+        self.config = config;
+        let (changed, lines): (Vec<_>, Vec<_>) = changes.into_iter().unzip();
+        for line in lines {
+            self.add_log(format!("Config reloaded: {line}"), false);
+        }
+        self.apply_reloaded_config(&changed);
+    }
+
+    /// Updates the state derived from the config, after it was replaced by a reload
+    ///
+    /// # Arguments
+    /// * `changed` - Names of the fields that differ from the previous config
+    fn apply_reloaded_config(&mut self, changed: &[String]) {
+        let is_changed = |field: &str| changed.iter().any(|name| name == field);
+
+        self.selected_action = self
+            .selected_action
+            .min(Action::all(&self.config).len().saturating_sub(1));
+
+        self.auto_threshold = self.config.active_threshold();
+        self.auto_action = self.config.active_auto_action().clone();
+
+        // Cooldowns are tracked by rule index, which no longer matches a changed list
+        let profile_changed = is_changed("profiles") || is_changed("active_profile");
+        if profile_changed || is_changed("rules") {
+            self.rule_last_fired.clear();
+        }
+        if profile_changed || is_changed("process_rules") {
+            self.process_rule_last_fired.clear();
+        }
+        if is_changed("cgroup_root") {
+            self.cgroup = None;
+            self.last_cgroup_refresh = None;
+            self.last_cgroup_tree_refresh = None;
+        }
+        if is_changed("notify_socket") {
+            self.start_notifier();
+        }
+        if is_changed("suspend") {
+            self.apply_suspend_change();
+        }
+        if is_changed("prewarm_paths") || is_changed("prewarm_lock_bytes") {
+            // Unlocks the files pinned under the old settings; a running pre-warm is
+            // abandoned so its mappings are dropped by the worker instead of kept here
+            let unlocked = !self.prewarm_locked.is_empty();
+            self.prewarm_locked.clear();
+            self.prewarm = None;
+            if unlocked {
+                self.add_log(
+                    "Pre-warm settings changed, unlocked previously locked files".to_string(),
+                    false,
+                );
+            }
+        }
+    }
+}
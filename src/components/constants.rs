@@ -1,5 +1,23 @@
 pub const LOG_CAPACITY: usize = 100;
+// The log file is moved to a single `.1` backup once it reaches this size
+pub const LOG_FILE_MAX_BYTES: u64 = 5 * 1024 * 1024;
 pub const CONFIG_FILE: &str = "ram_observer_config.json";
+pub const CONFIG_FILE_TOML: &str = "ram_observer_config.toml";
+// Config discovery: the variable naming a config file, and the per-user directory name
+pub const CONFIG_ENV_VAR: &str = "RAM_OBSERVER_CONFIG";
+pub const APP_DIR_NAME: &str = "ram-observer-rs";
+// Files kept in the data directory
+pub const LOG_FILE: &str = "ram_observer.log";
+pub const RAMMAP_EXECUTABLE: &str = "RAMMap64.exe";
+// Schema version written to the config; JSON files without a version are version 1
+pub const CONFIG_VERSION: u32 = 3;
+// Schema version TOML support was added in, which TOML files without a version are
+pub const TOML_CONFIG_VERSION: u32 = 2;
+// Name of the base settings when no profile is active
+pub const DEFAULT_PROFILE: &str = "default";
+// How long --profile waits for a running observer to confirm the switch, longer than the idle tick
+pub const PROFILE_SWITCH_TIMEOUT_SECS: u64 = 5;
+pub const DEFAULT_CGROUP_ROOT: &str = "/sys/fs/cgroup";
 
 // Cooldown timings
 pub const NAV_COOLDOWN_MS: u128 = 150;
@@ -11,6 +29,75 @@ pub const DEFAULT_AUTO_THRESHOLD: f32 = 90.0;
 pub const CRITICAL_THRESHOLD: f32 = 90.0;
 pub const WARNING_THRESHOLD: f32 = 75.0;
 
+// Last-resort process killer
+pub const KILLER_CHECK_MS: u128 = 1000;
+pub const DEFAULT_KILLER_SWAP_FREE_PERCENT: f32 = 10.0;
+pub const DEFAULT_KILLER_GRACE_SECS: u64 = 5;
+// Wait after a victim is gone before choosing another, so the kernel can free its memory
+pub const KILLER_SETTLE_MS: u128 = 3000;
+// How long a SIGKILLed victim is waited on, e.g. while stuck in uninterruptible sleep
+pub const KILLER_KILL_WAIT_SECS: u64 = 10;
+
+// Suspending low-priority processes under pressure
+pub const SUSPEND_CHECK_MS: u128 = 1000;
+pub const DEFAULT_MAX_SUSPEND_SECS: u64 = 600;
+// Stopped processes that are resumed on any exit; more are only resumed by the monitor
+pub const MAX_TRACKED_STOPPED: usize = 1024;
+
+// Sample history and forecasting
+pub const HISTORY_CAPACITY: usize = 600;
+pub const SAMPLE_INTERVAL_MS: u128 = 1000;
+pub const FORECAST_WINDOW_SECS: u64 = 300;
+pub const FORECAST_MIN_SAMPLES: usize = 10;
+// Growth in percentage points per second below which usage counts as stable
+pub const FORECAST_MIN_SLOPE: f64 = 1e-6;
+
+// Kernel statistics refresh intervals
+pub const CGROUP_REFRESH_MS: u128 = 1000;
+pub const CGROUP_TREE_REFRESH_MS: u128 = 2000;
+pub const OOM_CHECK_MS: u128 = 1000;
+pub const SWAP_REFRESH_MS: u128 = 2000;
+pub const NUMA_REFRESH_MS: u128 = 1000;
+pub const HUGEPAGES_REFRESH_MS: u128 = 2000;
+pub const FRAGMENTATION_REFRESH_MS: u128 = 2000;
+pub const SLAB_REFRESH_MS: u128 = 2000;
+pub const SYSCTL_REFRESH_MS: u128 = 2000;
+pub const CONFIG_RELOAD_CHECK_MS: u128 = 1000;
+
+// Fragmentation is measured for order-9 blocks (2MB huge pages with 4KB pages)
+pub const FRAGMENTATION_ORDER: usize = 9;
+pub const FRAGMENTATION_MIN_FREE_BLOCKS: u64 = 16;
+
+// Share of a cgroup's charged memory to reclaim per action
+pub const CGROUP_RECLAIM_PERCENT: u64 = 25;
+
+// Page cache scanning
+pub const PAGE_CACHE_MAX_FILES: usize = 200_000;
+pub const PAGE_CACHE_PROGRESS_INTERVAL: usize = 500;
+
+// Tunable vm sysctls
+pub const SYSCTL_VM_DIR: &str = "/proc/sys/vm";
+
+// Pressure notifications, graded by distance to the auto-execution threshold
+pub const NOTIFY_MODERATE_MARGIN: f32 = 10.0;
+pub const NOTIFY_CRITICAL_MARGIN: f32 = 2.0;
+pub const NOTIFY_MODERATE_FORECAST_SECS: u64 = 300;
+// Only the observer's user can connect to the notify socket
+pub const NOTIFY_SOCKET_MODE: u32 = 0o600;
+// Connections that don't send a registration line in time are closed
+pub const NOTIFY_REGISTRATION_TIMEOUT_SECS: u64 = 5;
+// Subscribers that leave this many notification bytes unread are dropped
+pub const NOTIFY_MAX_PENDING_BYTES: usize = 64 * 1024;
+
+// Per-process rules
+pub const PROCESS_RULES_CHECK_MS: u128 = 2000;
+
+// Keys bound by the TUI itself, which custom actions can't use as hotkeys
+pub const RESERVED_HOTKEYS: &str = "12345678qACPTRacgrsu+-";
+
+// Delay between a purge action and pre-warming the configured files
+pub const PREWARM_DELAY_MS: u128 = 2000;
+
 // Tickrates
 pub const ACTIVE_TICK_RATE_MS: u64 = 25;
 pub const IDLE_TICK_RATE_MS: u64 = 3000;
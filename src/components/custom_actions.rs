@@ -0,0 +1,180 @@
+use std::{
+    path::Path,
+    process::{Child, Command, ExitStatus},
+};
+
+use crate::components::{
+    procfs::is_root,
+    structs::{CustomAction, Privileges, RamMonitor},
+};
+
+/// Returns true if the config file can be modified by users other than its owner
+/// Custom actions run commands from the config, so such a file could be used to
+/// make the observer run arbitrary programs
+pub fn config_is_world_writable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        path.metadata()
+            .is_ok_and(|metadata| metadata.permissions().mode() & 0o022 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        false
+    }
+}
+
+/// Checks that a custom action can be run safely
+///
+/// # Arguments
+/// * `action` - The custom action to check
+/// * `names` - Names already used by other actions
+/// * `hotkeys` - Hotkeys already bound by the TUI or other custom actions
+///
+/// # Returns
+/// * `Ok(())` if the action is safe to run
+/// * `Err(String)` describing the first problem found
+pub fn validate_custom_action(
+    action: &CustomAction,
+    names: &[String],
+    hotkeys: &[char],
+) -> Result<(), String> {
+    let program = Path::new(&action.program);
+
+    if action.name.trim().is_empty() {
+        return Err("no name".to_string());
+    }
+    if names.contains(&action.name) {
+        return Err("a duplicate name".to_string());
+    }
+    if action.program.trim().is_empty() {
+        return Err("no program".to_string());
+    }
+    if !program.is_absolute() && program.components().count() > 1 {
+        return Err("a relative program path".to_string());
+    }
+    if action.privileges == Privileges::Root && !program.is_absolute() {
+        return Err("a root action without an absolute program path".to_string());
+    }
+    if std::iter::once(&action.program)
+        .chain(&action.args)
+        .any(|part| part.chars().any(char::is_control))
+    {
+        return Err("control characters in the command line".to_string());
+    }
+    if let Some(dir) = &action.working_dir {
+        let dir = Path::new(dir);
+        if !dir.is_absolute() || !dir.is_dir() {
+            return Err("a working_dir that is not an absolute directory".to_string());
+        }
+    }
+    check_hotkey(action.hotkey, hotkeys)
+}
+
+/// Checks that a configured hotkey is printable and not bound to anything else
+///
+/// # Arguments
+/// * `hotkey` - The configured hotkey, if any
+/// * `hotkeys` - Hotkeys already bound by the TUI, custom actions or macros
+pub fn check_hotkey(hotkey: Option<char>, hotkeys: &[char]) -> Result<(), String> {
+    match hotkey {
+        Some(hotkey) if hotkey.is_whitespace() || hotkey.is_control() => {
+            Err("an unusable hotkey".to_string())
+        }
+        Some(hotkey) if hotkeys.contains(&hotkey) => {
+            Err(format!("hotkey {hotkey} is already in use"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Describes how a finished command exited
+pub fn describe_status(status: ExitStatus) -> String {
+    status.code().map_or_else(
+        || "was terminated by a signal".to_string(),
+        |code| format!("exited with status {code}"),
+    )
+}
+
+impl RamMonitor {
+    /// Starts the custom action at the given index
+    /// The command runs in the background and its exit status is logged by poll_custom_actions
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `index` - Index into the configured custom_actions
+    pub fn run_custom_action(&mut self, index: usize) {
+        let Some(name) = self
+            .config
+            .custom_actions
+            .get(index)
+            .map(|action| action.name.clone())
+        else {
+            return;
+        };
+
+        if self.custom_runs.iter().any(|(running, _)| *running == name) {
+            self.add_log(format!("{name} is already running"), true);
+            return;
+        }
+        if let Some(child) = self.spawn_custom_action(index) {
+            self.custom_runs.push((name, child));
+        }
+    }
+
+    /// Starts the custom action at the given index without tracking it
+    ///
+    /// # Returns
+    /// * `Some(Child)` for the running command
+    /// * `None` if the action is missing, lacks privileges or failed to start, after logging why
+    pub fn spawn_custom_action(&mut self, index: usize) -> Option<Child> {
+        let action = self.config.custom_actions.get(index).cloned()?;
+
+        if action.privileges == Privileges::Root && cfg!(unix) && !is_root() {
+            self.add_log(format!("{} requires root", action.name), true);
+            return None;
+        }
+
+        let mut command = Command::new(&action.program);
+        command.args(&action.args);
+        if let Some(dir) = &action.working_dir {
+            command.current_dir(dir);
+        }
+
+        self.add_log(format!("Executing: {}...", action.name), false);
+        match command.spawn() {
+            Ok(child) => Some(child),
+            Err(e) => {
+                self.add_log(format!("Failed to execute {}: {e}", action.name), true);
+                None
+            }
+        }
+    }
+
+    /// Logs the outcome of custom actions that have finished
+    pub fn poll_custom_actions(&mut self) {
+        let mut messages = Vec::new();
+        self.custom_runs
+            .retain_mut(|(name, child)| match child.try_wait() {
+                Ok(None) => true,
+                Ok(Some(status)) if status.success() => {
+                    messages.push((format!("Successfully executed: {name}"), false));
+                    false
+                }
+                Ok(Some(status)) => {
+                    messages.push((format!("{name} {}", describe_status(status)), true));
+                    false
+                }
+                Err(e) => {
+                    messages.push((format!("Lost track of {name}: {e}"), true));
+                    false
+                }
+            });
+
+        for (message, is_error) in messages {
+            self.add_log(message, is_error);
+        }
+    }
+}
@@ -3,8 +3,8 @@ use std::time::Instant;
 
 use crate::components::{
     constants::{ACTION_COOLDOWN_MS, NAV_COOLDOWN_MS},
-    memory_management::Commands,
-    structs::RamMonitor,
+    memory_management::Action,
+    structs::{RamMonitor, View},
 };
 
 /// Determines if enough time has passed since the last action to allow a new action
@@ -46,7 +46,20 @@ fn can_process(last_time: Option<Instant>, current_time: Instant, cooldown: u128
 /// * `Enter` - Execute selected action
 /// * `Shift + A` - Cycle auto-action setting
 /// * `Shift + T` - Cycle auto-threshold setting
+/// * `Shift + C` - Toggle between host and cgroup memory view
+/// * `Tab / Shift + Tab` - Switch the bottom panel view
+/// * `PageUp/PageDown` - Navigate rows within the current view
+/// * `r` - Reclaim memory from the selected cgroup (cgroup view)
+/// * `s` - Cycle sort column (slab view)
+/// * `c` - Scan the configured directories (page cache view)
+/// * `g` - Toggle grouping by files or directories (page cache view)
+/// * `+/-` - Edit the selected sysctl (VM settings view)
+/// * `a` - Apply the edited sysctl value (VM settings view)
+/// * `r / Shift + R` - Revert the selected / all sysctls to startup values (VM settings view)
+/// * `u` - Resume the selected process (suspended processes view)
 /// * `1-5` - Hotkeys for direct action execution
+/// * `6-8` - Hotkeys for kernel memory actions (Linux)
+/// * Hotkeys configured for custom actions and macros
 pub fn handle_key_events(
     ram_monitor: &mut RamMonitor,
     key: KeyEvent,
@@ -68,14 +81,16 @@ pub fn handle_key_events(
         // Navigate down through actions
         (KeyCode::Down, _) if can_nav => {
             ram_monitor.selected_action =
-                (ram_monitor.selected_action + 1).min(Commands::ACTION_MAP.len() - 1); // Ensure we don't go out of bounds
+                (ram_monitor.selected_action + 1).min(Action::all(&ram_monitor.config).len() - 1); // Ensure we don't go out of bounds
             ram_monitor.last_key_press = Some(current_time);
         }
 
         // Execute selected action via enter key
         (KeyCode::Enter, _) if can_act => {
-            if let Some(command) = Commands::from_index(ram_monitor.selected_action) {
-                ram_monitor.run_rammap(command);
+            if let Some(action) =
+                Action::from_index(ram_monitor.selected_action, &ram_monitor.config)
+            {
+                ram_monitor.execute_action(action);
                 ram_monitor.last_action = Some(current_time);
             }
         }
@@ -92,10 +107,94 @@ pub fn handle_key_events(
             ram_monitor.last_key_press = Some(current_time);
         }
 
+        // Cycle profile
+        (KeyCode::Char('P'), m) if m.contains(KeyModifiers::SHIFT) && can_nav => {
+            ram_monitor.cycle_profile();
+            ram_monitor.last_key_press = Some(current_time);
+        }
+
+        // Toggle host / cgroup memory view
+        (KeyCode::Char('C'), m) if m.contains(KeyModifiers::SHIFT) && can_nav => {
+            ram_monitor.toggle_memory_scope();
+            ram_monitor.last_key_press = Some(current_time);
+        }
+
+        // Switch bottom panel view
+        (KeyCode::Tab, _) if can_nav => {
+            ram_monitor.cycle_view(true);
+            ram_monitor.last_key_press = Some(current_time);
+        }
+        (KeyCode::BackTab, _) if can_nav => {
+            ram_monitor.cycle_view(false);
+            ram_monitor.last_key_press = Some(current_time);
+        }
+
+        // Navigate rows within the current view
+        (KeyCode::PageUp, _) if can_nav => {
+            ram_monitor.move_view_selection(false);
+            ram_monitor.last_key_press = Some(current_time);
+        }
+        (KeyCode::PageDown, _) if can_nav => {
+            ram_monitor.move_view_selection(true);
+            ram_monitor.last_key_press = Some(current_time);
+        }
+
+        // Reclaim memory from the selected cgroup
+        (KeyCode::Char('r'), _) if ram_monitor.view == View::Cgroups && can_act => {
+            ram_monitor.reclaim_selected_cgroup();
+            ram_monitor.last_action = Some(current_time);
+        }
+
+        // Cycle slab cache sort column
+        (KeyCode::Char('s'), _) if ram_monitor.view == View::Slab && can_nav => {
+            ram_monitor.cycle_slab_sort();
+            ram_monitor.last_key_press = Some(current_time);
+        }
+
+        // Scan the page cache of the configured directories
+        (KeyCode::Char('c'), _) if ram_monitor.view == View::PageCache && can_act => {
+            ram_monitor.start_page_cache_scan();
+            ram_monitor.last_action = Some(current_time);
+        }
+
+        // Toggle page cache grouping between files and directories
+        (KeyCode::Char('g'), _) if ram_monitor.view == View::PageCache && can_nav => {
+            ram_monitor.toggle_page_cache_grouping();
+            ram_monitor.last_key_press = Some(current_time);
+        }
+
+        // Edit, apply and revert vm sysctls
+        (KeyCode::Char('+'), _) if ram_monitor.view == View::Sysctl && can_nav => {
+            ram_monitor.adjust_selected_sysctl(true);
+            ram_monitor.last_key_press = Some(current_time);
+        }
+        (KeyCode::Char('-'), _) if ram_monitor.view == View::Sysctl && can_nav => {
+            ram_monitor.adjust_selected_sysctl(false);
+            ram_monitor.last_key_press = Some(current_time);
+        }
+        (KeyCode::Char('a'), _) if ram_monitor.view == View::Sysctl && can_act => {
+            ram_monitor.apply_selected_sysctl();
+            ram_monitor.last_action = Some(current_time);
+        }
+        (KeyCode::Char('r'), _) if ram_monitor.view == View::Sysctl && can_act => {
+            ram_monitor.revert_sysctls(false);
+            ram_monitor.last_action = Some(current_time);
+        }
+        (KeyCode::Char('R'), _) if ram_monitor.view == View::Sysctl && can_act => {
+            ram_monitor.revert_sysctls(true);
+            ram_monitor.last_action = Some(current_time);
+        }
+
+        // Resume the selected suspended process
+        (KeyCode::Char('u'), _) if ram_monitor.view == View::Suspended && can_act => {
+            ram_monitor.resume_selected_process();
+            ram_monitor.last_action = Some(current_time);
+        }
+
         // Execute action via hotkey
         (KeyCode::Char(c), _) if can_act => {
-            if let Some(command) = Commands::from_char(c) {
-                ram_monitor.run_rammap(command);
+            if let Some(action) = Action::from_char(c, &ram_monitor.config) {
+                ram_monitor.execute_action(action);
                 ram_monitor.last_action = Some(current_time);
             }
         }
@@ -0,0 +1,182 @@
+use std::{
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, TryRecvError},
+    thread,
+};
+
+use glob::{Pattern, PatternError};
+
+use crate::components::{
+    constants::PAGE_CACHE_MAX_FILES,
+    page_cache::{cached_bytes, walk_files},
+    structs::{EvictionTarget, RamMonitor},
+    utils::format_bytes,
+};
+
+/// Outcome of evicting the page cache of a set of files
+#[derive(Default)]
+pub struct EvictionResult {
+    pub files: usize,
+    pub freed: u64,
+    pub errors: usize,
+}
+
+/// File selection for an eviction: roots to walk, filtered by include/exclude globs
+pub struct EvictionFilter {
+    pub roots: Vec<PathBuf>,
+    pub include: Vec<Pattern>,
+    pub exclude: Vec<Pattern>,
+}
+
+impl EvictionFilter {
+    /// Compiles the paths and glob patterns of an eviction target
+    pub fn from_target(target: &EvictionTarget) -> Result<Self, PatternError> {
+        Self::new(&target.paths, &target.include, &target.exclude)
+    }
+
+    /// Compiles roots and glob patterns given as strings
+    pub fn new(
+        paths: &[String],
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<Self, PatternError> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| Pattern::new(pattern))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            roots: paths.iter().map(PathBuf::from).collect(),
+            include: compile(include)?,
+            exclude: compile(exclude)?,
+        })
+    }
+
+    /// A file is selected if it matches any include pattern (or there are none)
+    /// and no exclude pattern
+    pub fn matches(&self, path: &Path) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.matches_path(path)))
+            && !self.exclude.iter().any(|p| p.matches_path(path))
+    }
+}
+
+/// Drops a file's clean pages from the page cache
+#[cfg(unix)]
+fn evict_file(file: &File) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    // SAFETY: posix_fadvise only takes an advisory hint on a valid, open file descriptor
+    let result = unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+    match result {
+        0 => Ok(()),
+        errno => Err(io::Error::from_raw_os_error(errno)),
+    }
+}
+
+#[cfg(not(unix))]
+fn evict_file(_file: &File) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "page cache eviction requires posix_fadvise",
+    ))
+}
+
+/// Evicts the page cache of every selected file, measuring residency before and after
+pub fn evict(filter: &EvictionFilter) -> EvictionResult {
+    let mut result = EvictionResult::default();
+
+    walk_files(&filter.roots, PAGE_CACHE_MAX_FILES, |path| {
+        if !filter.matches(path) {
+            return true;
+        }
+
+        let freed = File::open(path).and_then(|file| {
+            let size = file.metadata()?.len();
+            let before = cached_bytes(&file, size).unwrap_or(0);
+            evict_file(&file)?;
+            let after = cached_bytes(&file, size).unwrap_or(0);
+            Ok(before.saturating_sub(after))
+        });
+        match freed {
+            Ok(freed) => {
+                result.files += 1;
+                result.freed += freed;
+            }
+            Err(_) => result.errors += 1,
+        }
+        true
+    });
+
+    result
+}
+
+/// Formats an eviction result for the log
+pub fn describe_result(name: &str, result: &EvictionResult) -> String {
+    let errors = match result.errors {
+        0 => String::new(),
+        errors => format!(", {errors} files failed"),
+    };
+    format!(
+        "Evicted {name}: {} freed from {} files{errors}",
+        format_bytes(result.freed),
+        result.files
+    )
+}
+
+impl RamMonitor {
+    /// Evicts the page cache of a configured eviction target in a background worker
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `index` - Index of the target in the config's eviction_targets
+    pub fn run_eviction(&mut self, index: usize) {
+        let Some(target) = self.config.eviction_targets.get(index) else {
+            return;
+        };
+        let name = target.name.clone();
+        let filter = match EvictionFilter::from_target(target) {
+            Ok(filter) => filter,
+            Err(e) => {
+                self.add_log(format!("Invalid pattern in {name}: {e}"), true);
+                return;
+            }
+        };
+
+        self.add_log(format!("Executing: {name}..."), false);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(evict(&filter));
+        });
+        self.evictions.push((name, receiver));
+    }
+
+    /// Collects results from finished eviction workers and schedules a pre-warm afterwards
+    pub fn poll_evictions(&mut self) {
+        let mut finished = Vec::new();
+        let mut evicted = false;
+        self.evictions
+            .retain(|(name, receiver)| match receiver.try_recv() {
+                Ok(result) => {
+                    finished.push((describe_result(name, &result), false));
+                    evicted = true;
+                    false
+                }
+                Err(TryRecvError::Empty) => true,
+                Err(TryRecvError::Disconnected) => {
+                    finished.push((format!("Eviction of {name} stopped unexpectedly"), true));
+                    false
+                }
+            });
+
+        for (message, is_error) in finished {
+            self.add_log(message, is_error);
+        }
+        if evicted {
+            self.schedule_prewarm();
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+use crate::components::{
+    constants::{
+        FORECAST_MIN_SAMPLES, FORECAST_MIN_SLOPE, FORECAST_WINDOW_SECS, HISTORY_CAPACITY,
+        SAMPLE_INTERVAL_MS,
+    },
+    structs::{RamMonitor, Sample},
+    utils::is_due,
+};
+
+/// Computes the least-squares slope of RAM usage over time
+///
+/// # Arguments
+/// * `samples` - Samples ordered from oldest to newest
+///
+/// # Returns
+/// * Growth rate in percentage points per second
+/// * `None` if the samples don't span any time
+fn usage_slope(samples: &[&Sample]) -> Option<f64> {
+    let origin = samples.first()?.timestamp;
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .map(|s| {
+            (
+                s.timestamp.duration_since(origin).as_secs_f64(),
+                s.percentage as f64,
+            )
+        })
+        .collect();
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let (num, den) = points.iter().fold((0.0, 0.0), |(num, den), (x, y)| {
+        (
+            num + (x - mean_x) * (y - mean_y),
+            den + (x - mean_x) * (x - mean_x),
+        )
+    });
+
+    if den == 0.0 { None } else { Some(num / den) }
+}
+
+impl RamMonitor {
+    /// Records the current RAM usage into the sample history
+    /// Samples are taken at most once per SAMPLE_INTERVAL_MS
+    pub fn record_sample(&mut self, percentage: f32) {
+        if !is_due(
+            self.history.front().map(|s| s.timestamp),
+            SAMPLE_INTERVAL_MS,
+        ) {
+            return;
+        }
+
+        if self.history.len() >= HISTORY_CAPACITY {
+            self.history.pop_back();
+        }
+        self.history.push_front(Sample {
+            timestamp: Instant::now(),
+            percentage,
+        });
+    }
+
+    /// Estimates how long until RAM usage reaches the given percentage
+    ///
+    /// # Arguments
+    /// * `target` - The usage percentage to forecast
+    ///
+    /// # Returns
+    /// * `Some(Duration)` if usage is growing and below the target
+    /// * `None` if usage is stable, shrinking, already past the target,
+    ///   or there isn't enough history yet
+    pub fn forecast_time_to(&self, target: f32) -> Option<Duration> {
+        let current = self.history.front()?.percentage;
+        if current >= target {
+            return None;
+        }
+
+        let mut window: Vec<&Sample> = self
+            .history
+            .iter()
+            .take_while(|s| s.timestamp.elapsed().as_secs() <= FORECAST_WINDOW_SECS)
+            .collect();
+        if window.len() < FORECAST_MIN_SAMPLES {
+            return None;
+        }
+        window.reverse();
+
+        // Slopes this small are rounding noise of a flat history, and would overflow Duration
+        let slope = usage_slope(&window)?;
+        if slope <= FORECAST_MIN_SLOPE {
+            return None;
+        }
+
+        Duration::try_from_secs_f64((target - current) as f64 / slope).ok()
+    }
+}
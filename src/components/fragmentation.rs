@@ -0,0 +1,196 @@
+use std::{fs, time::Instant};
+
+use crate::components::{
+    constants::{FRAGMENTATION_MIN_FREE_BLOCKS, FRAGMENTATION_ORDER, FRAGMENTATION_REFRESH_MS},
+    structs::RamMonitor,
+    utils::is_due,
+};
+
+/// Free block counts per allocation order for one zone, optionally for a single migrate type
+pub struct ZoneFreeBlocks {
+    pub node: usize,
+    pub zone: String,
+    pub migrate_type: Option<String>,
+    pub counts: Vec<u64>,
+}
+
+impl ZoneFreeBlocks {
+    /// Total free pages in the zone, summed over all orders
+    pub fn free_pages(&self) -> u64 {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(order, count)| count << order)
+            .sum()
+    }
+
+    /// Free pages held in blocks of at least the given order
+    pub fn free_pages_at_order(&self, order: usize) -> u64 {
+        self.counts
+            .iter()
+            .enumerate()
+            .skip(order)
+            .map(|(order, count)| count << order)
+            .sum()
+    }
+
+    /// Unusable free space index: the percentage of free memory that can't
+    /// satisfy an allocation of the given order
+    pub fn fragmentation_index(&self, order: usize) -> Option<f32> {
+        unusable_index(self.free_pages(), self.free_pages_at_order(order))
+    }
+}
+
+/// Percentage of free pages that sit in blocks too small for the target order
+fn unusable_index(free_pages: u64, usable_pages: u64) -> Option<f32> {
+    (free_pages > 0).then(|| (free_pages - usable_pages) as f32 / free_pages as f32 * 100.0)
+}
+
+/// Fragmentation index over all zones at FRAGMENTATION_ORDER
+pub fn overall_fragmentation_index(zones: &[ZoneFreeBlocks]) -> Option<f32> {
+    let free: u64 = zones.iter().map(ZoneFreeBlocks::free_pages).sum();
+    let usable: u64 = zones
+        .iter()
+        .map(|zone| zone.free_pages_at_order(FRAGMENTATION_ORDER))
+        .sum();
+    unusable_index(free, usable)
+}
+
+/// Returns true when no high-order blocks are left although enough memory is free
+/// to form several of them, which is the situation compaction fixes
+pub fn is_compaction_recommended(zones: &[ZoneFreeBlocks]) -> bool {
+    let free: u64 = zones.iter().map(ZoneFreeBlocks::free_pages).sum();
+    let usable: u64 = zones
+        .iter()
+        .map(|zone| zone.free_pages_at_order(FRAGMENTATION_ORDER))
+        .sum();
+    usable == 0 && free >= FRAGMENTATION_MIN_FREE_BLOCKS << FRAGMENTATION_ORDER
+}
+
+/// Parses the counts following a "Node N, zone NAME" prefix
+fn parse_counts(fields: &[&str]) -> Vec<u64> {
+    fields
+        .iter()
+        .filter_map(|field| field.parse().ok())
+        .collect()
+}
+
+/// Reads the free block counts of every zone from /proc/buddyinfo
+pub fn read_buddyinfo() -> Vec<ZoneFreeBlocks> {
+    fs::read_to_string("/proc/buddyinfo")
+        .map_or_else(|_| Vec::new(), |contents| parse_buddyinfo(&contents))
+}
+
+/// Parses buddyinfo lines such as "Node 0, zone   Normal   1203 843 ..."
+fn parse_buddyinfo(contents: &str) -> Vec<ZoneFreeBlocks> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let ["Node", node, "zone", zone, counts @ ..] = fields.as_slice() else {
+                return None;
+            };
+            Some(ZoneFreeBlocks {
+                node: node.trim_end_matches(',').parse().ok()?,
+                zone: zone.to_string(),
+                migrate_type: None,
+                counts: parse_counts(counts),
+            })
+        })
+        .collect()
+}
+
+/// Parses the per-migrate-type free counts from /proc/pagetypeinfo
+///
+/// # Returns
+/// * Lines such as "Node 0, zone Normal, type Movable 12 4 ..."
+/// * An empty list when the file isn't readable (it is root-only on recent kernels)
+pub fn read_pagetypeinfo() -> Vec<ZoneFreeBlocks> {
+    let Ok(contents) = fs::read_to_string("/proc/pagetypeinfo") else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .take_while(|line| !line.starts_with("Number of blocks type"))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [
+                "Node",
+                node,
+                "zone",
+                zone,
+                "type",
+                migrate_type,
+                counts @ ..,
+            ] = fields.as_slice()
+            else {
+                return None;
+            };
+            Some(ZoneFreeBlocks {
+                node: node.trim_end_matches(',').parse().ok()?,
+                zone: zone.trim_end_matches(',').to_string(),
+                migrate_type: Some(migrate_type.to_string()),
+                counts: parse_counts(counts),
+            })
+        })
+        .collect()
+}
+
+impl RamMonitor {
+    /// Re-reads buddyinfo and pagetypeinfo at most once per FRAGMENTATION_REFRESH_MS
+    /// Logs a suggestion when compaction becomes recommended
+    pub fn refresh_fragmentation(&mut self) {
+        if !is_due(self.last_fragmentation_refresh, FRAGMENTATION_REFRESH_MS) {
+            return;
+        }
+
+        self.buddyinfo = read_buddyinfo();
+        self.pagetypeinfo = read_pagetypeinfo();
+        self.last_fragmentation_refresh = Some(Instant::now());
+
+        let recommended = is_compaction_recommended(&self.buddyinfo);
+        if recommended && !self.compaction_recommended {
+            self.add_log(
+                format!("No free order-{FRAGMENTATION_ORDER} blocks left, compaction recommended"),
+                false,
+            );
+        }
+        self.compaction_recommended = recommended;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Captured from a 6 GB virtual machine
+    const BUDDYINFO: &str = "\
+Node 0, zone      DMA      0      0      0      0      0      0      0      0      1      1      3 
+Node 0, zone    DMA32   2114   1463    847    663    396    225    153    110    105     79    625 
+Node 0, zone   Normal  10558   3033    937   1332    557    227    135     63     42     10     16 
+";
+
+    #[test]
+    fn parses_buddyinfo() {
+        let zones = parse_buddyinfo(BUDDYINFO);
+        assert_eq!(zones.len(), 3);
+
+        let normal = &zones[2];
+        assert_eq!(normal.node, 0);
+        assert_eq!(normal.zone, "Normal");
+        assert!(normal.migrate_type.is_none());
+        assert_eq!(
+            normal.counts,
+            vec![10558, 3033, 937, 1332, 557, 227, 135, 63, 42, 10, 16]
+        );
+        assert_eq!(zones[0].free_pages(), 256 + 512 + 3 * 1024);
+        assert_eq!(zones[0].free_pages_at_order(9), 512 + 3 * 1024);
+    }
+
+    #[test]
+    fn skips_malformed_buddyinfo_lines() {
+        let zones = parse_buddyinfo("Node x, zone Normal 1 2\ngarbage\n");
+        assert!(zones.is_empty());
+    }
+}
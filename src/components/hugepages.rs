@@ -0,0 +1,96 @@
+use std::{fs, time::Instant};
+
+use crate::components::{
+    constants::HUGEPAGES_REFRESH_MS,
+    procfs::{find_value, read_trimmed},
+    structs::RamMonitor,
+    utils::is_due,
+};
+
+/// Huge page pool and transparent huge page (THP) state
+pub struct HugePageStats {
+    pub total: u64,
+    pub free: u64,
+    pub reserved: u64,
+    pub surplus: u64,
+    pub page_size: u64,
+    pub anon_huge: u64,
+    pub shmem_huge: u64,
+    pub thp_enabled: Option<ThpMode>,
+    pub thp_defrag: Option<ThpMode>,
+}
+
+/// A THP setting, where the kernel lists all options and brackets the active one
+pub struct ThpMode {
+    pub current: String,
+    pub options: Vec<String>,
+}
+
+impl ThpMode {
+    /// Parses a THP sysfs file such as "always [madvise] never"
+    pub fn parse(contents: &str) -> Option<Self> {
+        let mut current = None;
+        let options = contents
+            .split_whitespace()
+            .map(
+                |option| match option.strip_prefix('[').and_then(|o| o.strip_suffix(']')) {
+                    Some(active) => {
+                        current = Some(active.to_string());
+                        active.to_string()
+                    }
+                    None => option.to_string(),
+                },
+            )
+            .collect();
+
+        Some(Self {
+            current: current?,
+            options,
+        })
+    }
+
+    /// Returns the option after the active one, wrapping around
+    pub fn next(&self) -> Option<&str> {
+        let index = self.options.iter().position(|o| *o == self.current)?;
+        self.options
+            .get((index + 1) % self.options.len())
+            .map(String::as_str)
+    }
+}
+
+/// Reads huge page counters from /proc/meminfo and THP modes from sysfs
+///
+/// # Returns
+/// * `None` if /proc/meminfo isn't available
+pub fn read_hugepages() -> Option<HugePageStats> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let value = |key: &str| find_value(&meminfo, key).unwrap_or(0);
+    let thp = |file: &str| {
+        read_trimmed(format!("/sys/kernel/mm/transparent_hugepage/{file}"))
+            .and_then(|contents| ThpMode::parse(&contents))
+    };
+
+    Some(HugePageStats {
+        total: value("HugePages_Total"),
+        free: value("HugePages_Free"),
+        reserved: value("HugePages_Rsvd"),
+        surplus: value("HugePages_Surp"),
+        page_size: value("Hugepagesize") * 1024,
+        anon_huge: value("AnonHugePages") * 1024,
+        shmem_huge: value("ShmemHugePages") * 1024,
+        thp_enabled: thp("enabled"),
+        thp_defrag: thp("defrag"),
+    })
+}
+
+impl RamMonitor {
+    /// Re-reads huge page statistics at most once per HUGEPAGES_REFRESH_MS
+    pub fn refresh_hugepages(&mut self) {
+        if !is_due(self.last_hugepages_refresh, HUGEPAGES_REFRESH_MS) {
+            return;
+        }
+
+        self.hugepages = read_hugepages();
+        self.last_hugepages_refresh = Some(Instant::now());
+    }
+}
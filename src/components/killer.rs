@@ -0,0 +1,246 @@
+use std::time::Instant;
+
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal};
+
+use crate::components::{
+    constants::{
+        DEFAULT_KILLER_GRACE_SECS, DEFAULT_KILLER_SWAP_FREE_PERCENT, KILLER_CHECK_MS,
+        KILLER_KILL_WAIT_SECS, KILLER_SETTLE_MS,
+    },
+    procfs::read_u64,
+    structs::{OomKillerConfig, RamMonitor, VictimPreference},
+    utils::{calculate_percentage, format_bytes, is_due},
+};
+
+/// A process the killer sent SIGTERM to and is waiting on before escalating
+pub struct KillerVictim {
+    pub pid: Pid,
+    pub name: String,
+    /// Start time of the process, telling it apart from a later process reusing the pid
+    pub start_time: u64,
+    pub terminated_at: Instant,
+    pub killed_at: Option<Instant>,
+}
+
+/// A process that may be killed, with the values victims are ranked by
+struct Candidate {
+    pid: Pid,
+    name: String,
+    start_time: u64,
+    rss: u64,
+    oom_score: u64,
+}
+
+/// Returns true when a process name passes the allow and deny lists
+/// An empty allow list allows every process that isn't denied
+fn is_eligible(killer: &OomKillerConfig, name: &str) -> bool {
+    (killer.allow.is_empty() || killer.allow.iter().any(|allowed| allowed == name))
+        && !killer.deny.iter().any(|denied| denied == name)
+}
+
+impl RamMonitor {
+    /// Returns available memory and free swap as percentages of their totals
+    /// Without swap, swap counts as fully used since it can't relieve pressure
+    fn memory_headroom(&self) -> (f32, f32) {
+        let available =
+            calculate_percentage(self.system.available_memory(), self.system.total_memory());
+        let swap_free = match self.system.total_swap() {
+            0 => 0.0,
+            total => calculate_percentage(self.system.free_swap(), total),
+        };
+        (available, swap_free)
+    }
+
+    /// Picks the eligible process with the highest oom_score or RSS
+    fn select_victim(&mut self, killer: &OomKillerConfig) -> Option<Candidate> {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_memory(),
+        );
+        let own_pid = sysinfo::get_current_pid().ok();
+
+        self.system
+            .processes()
+            .iter()
+            // Skip threads, kernel threads (no RSS), init and the observer itself
+            .filter(|(pid, process)| {
+                process.thread_kind().is_none()
+                    && process.memory() > 0
+                    && pid.as_u32() > 1
+                    && Some(**pid) != own_pid
+            })
+            .filter_map(|(pid, process)| {
+                let name = process.name().to_string_lossy().to_string();
+                is_eligible(killer, &name).then(|| Candidate {
+                    pid: *pid,
+                    name,
+                    start_time: process.start_time(),
+                    rss: process.memory(),
+                    oom_score: read_u64(format!("/proc/{pid}/oom_score")).unwrap_or(0),
+                })
+            })
+            .max_by_key(|candidate| match killer.prefer {
+                VictimPreference::OomScore => (candidate.oom_score, candidate.rss),
+                VictimPreference::Rss => (candidate.rss, candidate.oom_score),
+            })
+    }
+
+    /// Escalates to SIGKILL once the grace period after SIGTERM has passed, then waits for
+    /// the victim to exit
+    ///
+    /// # Returns
+    /// * `true` while the victim is still being waited on
+    fn follow_up_victim(&mut self, grace_secs: u64) -> bool {
+        let Some(victim) = &self.killer_victim else {
+            return false;
+        };
+        let (pid, name, killed_at) = (victim.pid, victim.name.clone(), victim.killed_at);
+        let elapsed = victim.terminated_at.elapsed();
+
+        self.system
+            .refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+        let start_time = victim.start_time;
+        let Some(process) = self
+            .system
+            .process(pid)
+            .filter(|process| process.start_time() == start_time)
+        else {
+            let signal = if killed_at.is_some() {
+                "SIGKILL"
+            } else {
+                "SIGTERM"
+            };
+            self.add_log(
+                format!(
+                    "{name} (pid {pid}) exited {:.1}s after {signal}",
+                    elapsed.as_secs_f32()
+                ),
+                false,
+            );
+            self.killer_victim = None;
+            self.last_killer_exit = Some(Instant::now());
+            return false;
+        };
+
+        if let Some(killed_at) = killed_at {
+            if killed_at.elapsed().as_secs() < KILLER_KILL_WAIT_SECS {
+                return true;
+            }
+            self.add_log(
+                format!("{name} (pid {pid}) still running {KILLER_KILL_WAIT_SECS}s after SIGKILL"),
+                true,
+            );
+            self.killer_victim = None;
+            self.last_killer_exit = Some(Instant::now());
+            return false;
+        }
+
+        if elapsed.as_secs() < grace_secs {
+            return true;
+        }
+        match process.kill_with(Signal::Kill) {
+            Some(true) => {
+                self.add_log(
+                    format!("{name} (pid {pid}) ignored SIGTERM for {grace_secs}s, sent SIGKILL"),
+                    true,
+                );
+                if let Some(victim) = &mut self.killer_victim {
+                    victim.killed_at = Some(Instant::now());
+                }
+                true
+            }
+            _ => {
+                self.add_log(
+                    format!("Failed to send SIGKILL to {name} (pid {pid})"),
+                    true,
+                );
+                self.killer_victim = None;
+                self.last_killer_exit = Some(Instant::now());
+                false
+            }
+        }
+    }
+
+    /// Terminates a process at most once per KILLER_CHECK_MS when memory is critically low
+    ///
+    /// # Behavior
+    /// * Only runs when `oom_killer` is configured
+    /// * Memory is critical when available memory and free swap are both below their levels
+    /// * The victim gets SIGTERM first and SIGKILL after the grace period
+    pub fn check_killer(&mut self) {
+        let Some(killer) = self.config.oom_killer.clone() else {
+            return;
+        };
+        if !is_due(self.last_killer_check, KILLER_CHECK_MS) {
+            return;
+        }
+        self.last_killer_check = Some(Instant::now());
+
+        let grace_secs = killer.grace_secs.unwrap_or(DEFAULT_KILLER_GRACE_SECS);
+        if self.follow_up_victim(grace_secs) {
+            return;
+        }
+        // Give the kernel time to free the last victim's memory before judging pressure again
+        if !is_due(self.last_killer_exit, KILLER_SETTLE_MS) {
+            return;
+        }
+
+        let (available, swap_free) = self.memory_headroom();
+        let swap_level = killer
+            .swap_free_percent
+            .unwrap_or(DEFAULT_KILLER_SWAP_FREE_PERCENT);
+        let critical = available < killer.mem_available_percent && swap_free <= swap_level;
+        if !critical {
+            self.killer_critical = false;
+            return;
+        }
+        let newly_critical = !self.killer_critical;
+        if newly_critical {
+            self.add_log(
+                format!("Memory critical: {available:.1}% available, {swap_free:.1}% swap free"),
+                true,
+            );
+            self.killer_critical = true;
+        }
+
+        let Some(victim) = self.select_victim(&killer) else {
+            if newly_critical {
+                self.add_log("No eligible process to terminate".to_string(), true);
+            }
+            return;
+        };
+        let sent = self
+            .system
+            .process(victim.pid)
+            .and_then(|process| process.kill_with(Signal::Term));
+        if sent != Some(true) {
+            self.add_log(
+                format!(
+                    "Failed to send SIGTERM to {} (pid {})",
+                    victim.name, victim.pid
+                ),
+                true,
+            );
+            return;
+        }
+
+        self.add_log(
+            format!(
+                "Sent SIGTERM to {} (pid {}, oom_score {}, RSS {})",
+                victim.name,
+                victim.pid,
+                victim.oom_score,
+                format_bytes(victim.rss)
+            ),
+            true,
+        );
+        self.killer_victim = Some(KillerVictim {
+            pid: victim.pid,
+            name: victim.name,
+            start_time: victim.start_time,
+            terminated_at: Instant::now(),
+            killed_at: None,
+        });
+    }
+}
@@ -0,0 +1,221 @@
+use std::time::{Duration, Instant};
+
+use crate::components::{
+    custom_actions::{check_hotkey, describe_status},
+    memory_management::{Action, ActionId},
+    structs::{ActionMacro, Config, MacroRun, RamMonitor},
+};
+
+/// Checks that a macro has steps that can all be run
+///
+/// # Arguments
+/// * `action_macro` - The macro to check
+/// * `names` - Names already used by other actions
+/// * `hotkeys` - Hotkeys already bound by the TUI, custom actions or other macros
+/// * `config` - Configuration providing the custom actions steps can refer to
+///
+/// # Returns
+/// * `Ok(())` if the macro is valid
+/// * `Err(String)` describing the first problem found
+pub fn validate_macro(
+    action_macro: &ActionMacro,
+    names: &[String],
+    hotkeys: &[char],
+    config: &Config,
+) -> Result<(), String> {
+    if action_macro.name.trim().is_empty() {
+        return Err("no name".to_string());
+    }
+    if names.contains(&action_macro.name) {
+        return Err("a duplicate name".to_string());
+    }
+    if action_macro.steps.is_empty() {
+        return Err("no steps".to_string());
+    }
+    for step in &action_macro.steps {
+        let action = Action::from_id(&step.action, config).map_err(|e| format!("step {e}"))?;
+        if !action.is_macro_step() {
+            return Err(format!(
+                "step {} is not a RAMMap command or custom action",
+                action.display_name(config)
+            ));
+        }
+    }
+
+    check_hotkey(action_macro.hotkey, hotkeys)
+}
+
+/// Returns the display name of a step's action, or its identifier if it no longer exists
+fn step_name(id: &ActionId, config: &Config) -> String {
+    Action::from_id(id, config).map_or_else(
+        |_| id.describe(),
+        |action| action.display_name(config).to_string(),
+    )
+}
+
+impl RamMonitor {
+    /// Starts the macro at the given index
+    /// Steps are run one after another by poll_macro, each waiting for the previous one to exit
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `index` - Index into the configured macros
+    pub fn start_macro(&mut self, index: usize) {
+        let Some(action_macro) = self.config.macros.get(index) else {
+            return;
+        };
+        let name = action_macro.name.clone();
+        let steps = action_macro.steps.len();
+
+        if let Some(running) = &self.macro_run {
+            let running = Action::Macro(running.index)
+                .display_name(&self.config)
+                .to_string();
+            self.add_log(format!("Macro {running} is still running"), true);
+            return;
+        }
+
+        self.add_log(format!("Running macro {name} ({steps} steps)"), false);
+        self.macro_run = Some(MacroRun {
+            index,
+            step: 0,
+            step_started: Instant::now(),
+            child: None,
+            failures: 0,
+        });
+        self.poll_macro();
+    }
+
+    /// Advances the running macro: collects the current step's exit status, then starts the
+    /// next step once its delay has passed
+    pub fn poll_macro(&mut self) {
+        let Some(run) = &mut self.macro_run else {
+            return;
+        };
+        let Some(action_macro) = self.config.macros.get(run.index).cloned() else {
+            self.macro_run = None;
+            return;
+        };
+        let total = action_macro.steps.len();
+
+        if let Some(child) = &mut run.child {
+            let status = match child.try_wait() {
+                Ok(None) => return,
+                Ok(Some(status)) if status.success() => Ok(()),
+                Ok(Some(status)) => Err(describe_status(status)),
+                Err(e) => Err(format!("could not be waited for: {e}")),
+            };
+            run.child = None;
+            let step = run.step;
+            run.step += 1;
+            run.step_started = Instant::now();
+            self.finish_macro_step(&action_macro, step, status);
+            return;
+        }
+
+        if run.step >= total {
+            let failures = run.failures;
+            self.macro_run = None;
+            self.add_log(
+                match failures {
+                    0 => format!("Macro {} finished", action_macro.name),
+                    failures => format!(
+                        "Macro {} finished with {failures} failed steps",
+                        action_macro.name
+                    ),
+                },
+                failures > 0,
+            );
+            return;
+        }
+
+        let step = &action_macro.steps[run.step];
+        let delay = Duration::from_secs(step.delay_secs.unwrap_or(0));
+        if run.step_started.elapsed() < delay {
+            return;
+        }
+
+        let index = run.step;
+        let action = Action::from_id(&step.action, &self.config);
+        self.add_log(
+            format!(
+                "Macro {} step {}/{total}: {}",
+                action_macro.name,
+                index + 1,
+                step_name(&step.action, &self.config)
+            ),
+            false,
+        );
+        let child = match action {
+            Ok(Action::RamMap(cmd)) => self.spawn_rammap(cmd),
+            Ok(Action::Custom(custom)) => self.spawn_custom_action(custom),
+            _ => None,
+        };
+
+        match child {
+            Some(child) => {
+                if let Some(run) = &mut self.macro_run {
+                    run.child = Some(child);
+                }
+            }
+            None => {
+                if let Some(run) = &mut self.macro_run {
+                    run.step += 1;
+                    run.step_started = Instant::now();
+                }
+                self.finish_macro_step(&action_macro, index, Err("failed to start".to_string()));
+            }
+        }
+    }
+
+    /// Logs the outcome of a macro step and stops the macro if a failed step should end it
+    ///
+    /// # Arguments
+    /// * `action_macro` - The running macro
+    /// * `step` - Index of the finished step
+    /// * `status` - The step's outcome, with a description of the failure
+    fn finish_macro_step(
+        &mut self,
+        action_macro: &ActionMacro,
+        step: usize,
+        status: Result<(), String>,
+    ) {
+        let id = &action_macro.steps[step].action;
+        let action = step_name(id, &self.config);
+        match status {
+            Ok(()) => {
+                self.add_log(
+                    format!(
+                        "Macro {} step {}: {action} succeeded",
+                        action_macro.name,
+                        step + 1
+                    ),
+                    false,
+                );
+                if matches!(Action::from_id(id, &self.config), Ok(Action::RamMap(_))) {
+                    self.schedule_prewarm();
+                }
+            }
+            Err(reason) => {
+                self.add_log(
+                    format!(
+                        "Macro {} step {}: {action} {reason}",
+                        action_macro.name,
+                        step + 1
+                    ),
+                    true,
+                );
+                if let Some(run) = &mut self.macro_run {
+                    run.failures += 1;
+                }
+                if !action_macro.continue_on_failure {
+                    self.macro_run = None;
+                    self.add_log(
+                        format!("Macro {} stopped after a failed step", action_macro.name),
+                        true,
+                    );
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,14 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::components::structs::Config;
+
 /// Represents available RAM management commands that can be executed via RAMMap64.exe
 /// Each variant corresponds to a specific memory clearing operation
+/// Serialized in the config as snake_case identifiers, e.g. "empty_standby_list"
 #[allow(clippy::enum_variant_names)]
-#[derive(Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
 pub enum Commands {
     EmptyWorkingSets,
     EmptySystemWorkingSets,
@@ -49,18 +56,6 @@ impl Commands {
         }
     }
 
-    /// Retrieves a command by its index in the ACTION_MAP
-    ///
-    /// # Arguments
-    /// * `index` - The index to look up
-    ///
-    /// # Returns
-    /// * `Some(Commands)` if index is valid
-    /// * `None` if index is out of bounds
-    pub fn from_index(index: usize) -> Option<Self> {
-        Self::ACTION_MAP.get(index).map(|(_, cmd)| *cmd)
-    }
-
     /// Retrieves a command by its associated hotkey character
     ///
     /// # Arguments
@@ -76,3 +71,269 @@ impl Commands {
             .map(|(_, cmd)| *cmd)
     }
 }
+
+/// Represents memory management commands that target a single cgroup v2 group
+/// Each variant corresponds to a write to one of the group's control files
+pub enum CgroupCommands {
+    Reclaim { group: PathBuf, bytes: u64 },
+}
+
+impl CgroupCommands {
+    /// Returns the cgroup directory this command targets
+    pub fn group(&self) -> &Path {
+        match self {
+            Self::Reclaim { group, .. } => group,
+        }
+    }
+
+    /// Returns the control file path this command writes to
+    pub fn control_file(&self) -> PathBuf {
+        match self {
+            Self::Reclaim { group, .. } => group.join("memory.reclaim"),
+        }
+    }
+
+    /// Returns the value written to the control file
+    pub fn value(&self) -> String {
+        match self {
+            Self::Reclaim { bytes, .. } => bytes.to_string(),
+        }
+    }
+
+    /// Returns a human-readable name for the command
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Reclaim { .. } => "Reclaim Cgroup Memory",
+        }
+    }
+}
+
+/// Represents Linux kernel memory management commands, executed by writing to procfs/sysfs
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KernelCommands {
+    CompactMemory,
+    CycleThpEnabled,
+    CycleThpDefrag,
+}
+
+impl KernelCommands {
+    /// Maps keyboard characters to KernelCommands, continuing after the RAMMap hotkeys
+    pub const ACTION_MAP: [(char, KernelCommands); 3] = [
+        ('6', KernelCommands::CompactMemory),
+        ('7', KernelCommands::CycleThpEnabled),
+        ('8', KernelCommands::CycleThpDefrag),
+    ];
+
+    /// Returns the procfs/sysfs file this command writes to
+    pub fn control_file(&self) -> &str {
+        match self {
+            Self::CompactMemory => "/proc/sys/vm/compact_memory",
+            Self::CycleThpEnabled => "/sys/kernel/mm/transparent_hugepage/enabled",
+            Self::CycleThpDefrag => "/sys/kernel/mm/transparent_hugepage/defrag",
+        }
+    }
+
+    /// Returns a human-readable name for the command
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::CompactMemory => "Compact Memory",
+            Self::CycleThpEnabled => "Cycle THP Enabled Mode",
+            Self::CycleThpDefrag => "Cycle THP Defrag Mode",
+        }
+    }
+}
+
+/// Stable identifier of an action in the config
+/// Built-in commands are written as identifiers such as "empty_standby_list", actions defined
+/// in the config by kind and name, e.g. {"custom": "Restart Browser Helper"}
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionId {
+    Prewarm,
+    Evict(String),
+    Custom(String),
+    Macro(String),
+    #[serde(untagged)]
+    RamMap(Commands),
+    #[serde(untagged)]
+    Kernel(KernelCommands),
+    /// An unrecognized action name, kept so validation can report it
+    #[serde(untagged)]
+    Name(String),
+}
+
+impl ActionId {
+    /// Returns the identifier as it is written in the config
+    pub fn describe(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Any action listed in the Memory Management panel
+#[derive(Clone, Copy)]
+pub enum Action {
+    RamMap(Commands),
+    Kernel(KernelCommands),
+    /// Reads the configured prewarm_paths back into the page cache
+    Prewarm,
+    /// Page cache eviction of the configured eviction target at this index
+    Evict(usize),
+    /// The configured custom action at this index
+    Custom(usize),
+    /// The configured macro at this index
+    Macro(usize),
+}
+
+impl Action {
+    /// Returns every available action in display order
+    /// Kernel commands are only listed on Linux, followed by pre-warming (if prewarm_paths
+    /// are configured), the configured eviction targets, custom actions and macros
+    pub fn all(config: &Config) -> Vec<Action> {
+        let rammap = Commands::ACTION_MAP
+            .iter()
+            .map(|(_, cmd)| Action::RamMap(*cmd));
+        let kernel = KernelCommands::ACTION_MAP
+            .iter()
+            .filter(|_| cfg!(target_os = "linux"))
+            .map(|(_, cmd)| Action::Kernel(*cmd));
+        let prewarm = (!config.prewarm_paths.is_empty()).then_some(Action::Prewarm);
+        let evict = (0..config.eviction_targets.len()).map(Action::Evict);
+        let custom = (0..config.custom_actions.len()).map(Action::Custom);
+        let macros = (0..config.macros.len()).map(Action::Macro);
+        rammap
+            .chain(kernel)
+            .chain(prewarm)
+            .chain(evict)
+            .chain(custom)
+            .chain(macros)
+            .collect()
+    }
+
+    /// Returns a human-readable name for the action
+    /// Eviction targets are named after their configured name
+    pub fn display_name<'a>(&'a self, config: &'a Config) -> &'a str {
+        match self {
+            Self::RamMap(cmd) => cmd.display_name(),
+            Self::Kernel(cmd) => cmd.display_name(),
+            Self::Prewarm => "Pre-warm Page Cache",
+            Self::Evict(index) => config
+                .eviction_targets
+                .get(*index)
+                .map_or("Evict Page Cache", |target| target.name.as_str()),
+            Self::Custom(index) => config
+                .custom_actions
+                .get(*index)
+                .map_or("Custom Action", |action| action.name.as_str()),
+            Self::Macro(index) => config
+                .macros
+                .get(*index)
+                .map_or("Macro", |action_macro| action_macro.name.as_str()),
+        }
+    }
+
+    /// Returns the config identifier of the action
+    pub fn id(&self, config: &Config) -> ActionId {
+        match self {
+            Self::RamMap(cmd) => ActionId::RamMap(*cmd),
+            Self::Kernel(cmd) => ActionId::Kernel(*cmd),
+            Self::Prewarm => ActionId::Prewarm,
+            Self::Evict(_) => ActionId::Evict(self.display_name(config).to_string()),
+            Self::Custom(_) => ActionId::Custom(self.display_name(config).to_string()),
+            Self::Macro(_) => ActionId::Macro(self.display_name(config).to_string()),
+        }
+    }
+
+    /// Retrieves the action a config identifier refers to
+    ///
+    /// # Returns
+    /// * `Ok(Action)` if the action is available
+    /// * `Err(String)` explaining why the identifier doesn't name an available action
+    pub fn from_id(id: &ActionId, config: &Config) -> Result<Self, String> {
+        if let Some(action) = Self::all(config)
+            .into_iter()
+            .find(|action| action.id(config) == *id)
+        {
+            return Ok(action);
+        }
+
+        Err(match id {
+            ActionId::Name(name) => {
+                let builtin: Vec<String> = Self::all(&Config::default())
+                    .iter()
+                    .map(|action| action.id(config).describe())
+                    .collect();
+                format!(
+                    "unknown action \"{name}\", expected one of {} or a \
+                     {{\"custom\"|\"evict\"|\"macro\": name}} object",
+                    builtin.join(", ")
+                )
+            }
+            ActionId::Kernel(_) => "kernel commands are only available on Linux".to_string(),
+            ActionId::Evict(name) => format!("no eviction target named \"{name}\""),
+            ActionId::Custom(name) => format!("no custom action named \"{name}\""),
+            ActionId::Macro(name) => format!("no macro named \"{name}\""),
+            ActionId::Prewarm => "pre-warming needs prewarm_paths".to_string(),
+            ActionId::RamMap(_) => format!("{} is not available", id.describe()),
+        })
+    }
+
+    /// Returns the hotkey configured for a custom action or macro
+    pub fn configured_hotkey(&self, config: &Config) -> Option<char> {
+        match self {
+            Self::Custom(index) => config.custom_actions.get(*index)?.hotkey,
+            Self::Macro(index) => config.macros.get(*index)?.hotkey,
+            _ => None,
+        }
+    }
+
+    /// Returns true for actions that can be selected as the auto-execution action
+    pub fn is_auto_action(&self) -> bool {
+        matches!(self, Self::RamMap(_) | Self::Custom(_) | Self::Macro(_))
+    }
+
+    /// Returns true for actions that can be a step of a macro
+    pub fn is_macro_step(&self) -> bool {
+        matches!(self, Self::RamMap(_) | Self::Custom(_))
+    }
+
+    /// Returns the actions Shift+A cycles through, in display order
+    pub fn auto_actions(config: &Config) -> Vec<Action> {
+        Self::all(config)
+            .into_iter()
+            .filter(Action::is_auto_action)
+            .collect()
+    }
+
+    /// Retrieves an action by its index in the Memory Management list
+    ///
+    /// # Arguments
+    /// * `index` - The index to look up
+    /// * `config` - Configuration providing the eviction targets
+    ///
+    /// # Returns
+    /// * `Some(Action)` if index is valid
+    /// * `None` if index is out of bounds
+    pub fn from_index(index: usize, config: &Config) -> Option<Self> {
+        Self::all(config).get(index).copied()
+    }
+
+    /// Retrieves an action by its associated hotkey character
+    /// Custom actions and macros use the hotkeys defined in the config
+    pub fn from_char(c: char, config: &Config) -> Option<Self> {
+        Commands::from_char(c)
+            .map(Action::RamMap)
+            .or_else(|| {
+                KernelCommands::ACTION_MAP
+                    .iter()
+                    .filter(|_| cfg!(target_os = "linux"))
+                    .find(|(key, _)| *key == c)
+                    .map(|(_, cmd)| Action::Kernel(*cmd))
+            })
+            .or_else(|| {
+                Self::all(config)
+                    .into_iter()
+                    .find(|action| action.configured_hotkey(config) == Some(c))
+            })
+    }
+}
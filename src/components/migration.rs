@@ -0,0 +1,293 @@
+use serde_json::{Map, Value};
+
+use crate::components::{
+    config_file::ConfigMessage,
+    constants::CONFIG_VERSION,
+    memory_management::{Action, ActionId},
+    structs::Config,
+};
+
+/// Upgrades a config from one schema version to the next, returning notes on what the user
+/// should know about the changes
+type Migration = fn(&mut Map<String, Value>) -> Vec<ConfigMessage>;
+
+/// Builds the identifier of an action defined in the config from its name
+type NamedAction = fn(String) -> ActionId;
+
+/// Migrations indexed by the version they upgrade from, starting at version 1
+const MIGRATIONS: [Migration; (CONFIG_VERSION - 1) as usize] =
+    [typed_action_ids, unbind_profile_hotkey];
+
+/// Returns the schema version of a config, treating files without one as version 1
+pub fn config_version(config: &Map<String, Value>) -> u32 {
+    config
+        .get("version")
+        .and_then(Value::as_u64)
+        .and_then(|version| u32::try_from(version).ok())
+        .map_or(1, |version| version.max(1))
+}
+
+/// Runs every migration from the config's version up to CONFIG_VERSION
+///
+/// # Arguments
+/// * `config` - The config file's top-level object
+/// * `notes` - Receives the migrations' notes about the changes
+///
+/// # Returns
+/// * `Some(version)` with the version the config was upgraded from
+/// * `None` if the config was already current
+pub fn migrate(config: &mut Map<String, Value>, notes: &mut Vec<ConfigMessage>) -> Option<u32> {
+    let from = config_version(config);
+    if from >= CONFIG_VERSION {
+        return None;
+    }
+
+    for migration in &MIGRATIONS[(from - 1) as usize..] {
+        notes.extend(migration(config));
+    }
+    config.insert("version".to_string(), Value::from(CONFIG_VERSION));
+    Some(from)
+}
+
+/// Lists the top-level fields a migration changed, apart from the version itself
+///
+/// # Arguments
+/// * `before` - The config file's top-level object as it was read
+/// * `after` - The same object after `migrate`
+pub fn migrated_fields(before: &Map<String, Value>, after: &Map<String, Value>) -> Vec<String> {
+    after
+        .iter()
+        .filter(|(key, value)| *key != "version" && before.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect()
+}
+
+/// Version 1 to 2: actions were referred to by display name and are now ActionIds
+fn typed_action_ids(config: &mut Map<String, Value>) -> Vec<ConfigMessage> {
+    let builtin = Config::default();
+    let mut ids: Vec<(String, ActionId)> = Action::all(&builtin)
+        .iter()
+        .chain([&Action::Prewarm])
+        .map(|action| {
+            (
+                action.display_name(&builtin).to_string(),
+                action.id(&builtin),
+            )
+        })
+        .collect();
+
+    let user_defined: [(&str, NamedAction); 3] = [
+        ("eviction_targets", ActionId::Evict),
+        ("custom_actions", ActionId::Custom),
+        ("macros", ActionId::Macro),
+    ];
+    for (field, id) in user_defined {
+        let names = config
+            .get(field)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.get("name")?.as_str());
+        ids.extend(names.map(|name| (name.to_string(), id(name.to_string()))));
+    }
+
+    let rename = |value: &mut Value| {
+        let id = value
+            .as_str()
+            .and_then(|name| ids.iter().find(|(display_name, _)| display_name == name))
+            .and_then(|(_, id)| serde_json::to_value(id).ok());
+        if let Some(id) = id {
+            *value = id;
+        }
+    };
+
+    if let Some(action) = config.get_mut("auto_action") {
+        rename(action);
+    }
+    for rule in array_mut(config, "rules") {
+        if let Some(action) = rule.get_mut("action") {
+            rename(action);
+        }
+    }
+    for action_macro in array_mut(config, "macros") {
+        let steps = action_macro
+            .get_mut("steps")
+            .and_then(Value::as_array_mut)
+            .into_iter()
+            .flatten();
+        for step in steps {
+            if let Some(action) = step.get_mut("action") {
+                rename(action);
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// Version 2 to 3: Shift+P switches profiles, so a custom action or macro bound to P loses its
+/// hotkey instead of being disabled for the conflict
+fn unbind_profile_hotkey(config: &mut Map<String, Value>) -> Vec<ConfigMessage> {
+    let mut notes = Vec::new();
+    for field in ["custom_actions", "macros"] {
+        for (index, entry) in array_mut(config, field).enumerate() {
+            let Some(entry) = entry.as_object_mut() else {
+                continue;
+            };
+            if entry.get("hotkey").and_then(Value::as_str) != Some("P") {
+                continue;
+            }
+            entry.remove("hotkey");
+            let name = entry
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            notes.push(ConfigMessage::entry(
+                field,
+                index,
+                format!("Hotkey P of {name} now switches profiles, the hotkey was removed"),
+                false,
+            ));
+        }
+    }
+    notes
+}
+
+/// Returns the entries of an array field, or nothing if the field isn't an array
+fn array_mut<'a>(
+    config: &'a mut Map<String, Value>,
+    field: &str,
+) -> impl Iterator<Item = &'a mut Value> {
+    config
+        .get_mut(field)
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn object(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(fields) => fields,
+            _ => panic!("not an object"),
+        }
+    }
+
+    #[test]
+    fn config_version_defaults_to_one() {
+        assert_eq!(config_version(&object(json!({}))), 1);
+        assert_eq!(config_version(&object(json!({ "version": 0 }))), 1);
+        assert_eq!(config_version(&object(json!({ "version": "2" }))), 1);
+        assert_eq!(config_version(&object(json!({ "version": 3 }))), 3);
+    }
+
+    #[test]
+    fn migrate_leaves_current_configs_alone() {
+        let mut config =
+            object(json!({ "version": CONFIG_VERSION, "auto_action": "Empty Standby List" }));
+        let before = config.clone();
+        assert_eq!(migrate(&mut config, &mut Vec::new()), None);
+        assert_eq!(config, before);
+    }
+
+    #[test]
+    fn migrate_upgrades_unversioned_configs() {
+        let mut config = object(json!({ "auto_action": "Empty Standby List" }));
+        assert_eq!(migrate(&mut config, &mut Vec::new()), Some(1));
+        assert_eq!(config["version"], json!(CONFIG_VERSION));
+        assert_eq!(config["auto_action"], json!("empty_standby_list"));
+    }
+
+    #[test]
+    fn typed_action_ids_renames_every_action_reference() {
+        let mut config = object(json!({
+            "auto_action": "Restart Helper",
+            "rules": [
+                { "trigger": { "type": "oom_kill" }, "action": "Empty Working Sets" },
+                { "trigger": { "type": "oom_kill" }, "action": "Deep Clean" }
+            ],
+            "custom_actions": [{ "name": "Restart Helper", "program": "true" }],
+            "eviction_targets": [{ "name": "Logs", "paths": ["/var/log"] }],
+            "macros": [{
+                "name": "Deep Clean",
+                "steps": [
+                    { "action": "Empty Standby List" },
+                    { "action": "Logs" },
+                    { "action": "Not An Action" }
+                ]
+            }]
+        }));
+        assert!(typed_action_ids(&mut config).is_empty());
+
+        assert_eq!(config["auto_action"], json!({ "custom": "Restart Helper" }));
+        assert_eq!(config["rules"][0]["action"], json!("empty_working_sets"));
+        assert_eq!(
+            config["rules"][1]["action"],
+            json!({ "macro": "Deep Clean" })
+        );
+        let steps = &config["macros"][0]["steps"];
+        assert_eq!(steps[0]["action"], json!("empty_standby_list"));
+        assert_eq!(steps[1]["action"], json!({ "evict": "Logs" }));
+        // Unknown names are left for validation to report
+        assert_eq!(steps[2]["action"], json!("Not An Action"));
+    }
+
+    #[test]
+    fn typed_action_ids_keeps_identifiers() {
+        let mut config = object(json!({
+            "auto_action": "empty_standby_list",
+            "rules": [{ "trigger": { "type": "oom_kill" }, "action": { "custom": "x" } }]
+        }));
+        let before = config.clone();
+        assert!(typed_action_ids(&mut config).is_empty());
+        assert_eq!(config, before);
+    }
+
+    #[test]
+    fn unbind_profile_hotkey_keeps_the_actions() {
+        let mut config = object(json!({
+            "version": 2,
+            "custom_actions": [
+                { "name": "Helper", "program": "true", "hotkey": "P" },
+                { "name": "Other", "program": "true", "hotkey": "p" }
+            ],
+            "macros": [{ "name": "Clean", "steps": [], "hotkey": "P" }]
+        }));
+        let mut notes = Vec::new();
+        assert_eq!(migrate(&mut config, &mut notes), Some(2));
+
+        assert_eq!(config["custom_actions"][0].get("hotkey"), None);
+        assert_eq!(config["custom_actions"][1]["hotkey"], json!("p"));
+        assert_eq!(config["macros"][0].get("hotkey"), None);
+        let located: Vec<_> = notes
+            .iter()
+            .map(|note| (note.field.as_deref(), note.entry, note.is_error))
+            .collect();
+        assert_eq!(
+            located,
+            vec![
+                (Some("custom_actions"), Some(0), false),
+                (Some("macros"), Some(0), false)
+            ]
+        );
+    }
+
+    #[test]
+    fn migrated_fields_ignores_the_version() {
+        let before = object(json!({ "auto_action": "Empty Standby List", "rules": [] }));
+        let mut after = before.clone();
+        migrate(&mut after, &mut Vec::new());
+        assert_eq!(
+            migrated_fields(&before, &after),
+            vec!["auto_action".to_string()]
+        );
+
+        let before = object(json!({ "auto_action": "empty_standby_list" }));
+        let mut after = before.clone();
+        migrate(&mut after, &mut Vec::new());
+        assert!(migrated_fields(&before, &after).is_empty());
+    }
+}
@@ -1,9 +1,36 @@
 pub mod actions;
+pub mod cgroup;
+pub mod cli;
+pub mod config_file;
 pub mod config_handler;
+pub mod config_reload;
 pub mod constants;
+pub mod custom_actions;
 pub mod event_handler;
+pub mod eviction;
+pub mod forecast;
+pub mod fragmentation;
+pub mod hugepages;
+pub mod killer;
+pub mod macros;
 pub mod memory_management;
+pub mod migration;
 pub mod monitor;
+pub mod notify;
+pub mod notify_client;
+pub mod numa;
+pub mod oom;
+pub mod page_cache;
+pub mod paths;
+pub mod prewarm;
+pub mod process_rules;
+pub mod procfs;
+pub mod profiles;
+pub mod rules;
+pub mod slab;
 pub mod structs;
+pub mod suspend;
+pub mod swap;
+pub mod sysctl;
 pub mod ui;
 pub mod utils;
@@ -1,39 +1,129 @@
 use ratatui::Frame;
 use sysinfo::System;
 
-use std::{collections::VecDeque, time::Instant};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    time::{Instant, SystemTime},
+};
 
 use crate::components::{
     constants::{
-        ACTIVE_TICK_RATE_MS, DEFAULT_AUTO_THRESHOLD, IDLE_THRESHOLD_MS, IDLE_TICK_RATE_MS,
-        LOG_CAPACITY,
+        ACTIVE_TICK_RATE_MS, DEFAULT_AUTO_THRESHOLD, HISTORY_CAPACITY, IDLE_THRESHOLD_MS,
+        IDLE_TICK_RATE_MS, LOG_CAPACITY, LOG_FILE_MAX_BYTES,
     },
-    memory_management::Commands,
-    structs::{ActivityState, Config, LogEntry, RamMonitor},
+    fragmentation::overall_fragmentation_index,
+    memory_management::{Action, ActionId, Commands},
+    notify_client::PressureLevel,
+    oom::KernelLog,
+    page_cache::CacheGrouping,
+    paths::Paths,
+    rules::RuleContext,
+    slab::SlabSort,
+    structs::{ActivityState, Config, LogEntry, MemoryScope, RamMonitor, View},
+    sysctl::read_sysctls,
     ui,
-    utils::{self, bytes_to_gb, calculate_percentage},
+    utils::{bytes_to_gb, calculate_percentage, format_utc},
 };
 
+/// Opens the log file in the data directory for appending, creating the directory if needed
+fn open_log_file(paths: &Paths) -> Option<File> {
+    if !paths.data_dir.as_os_str().is_empty() {
+        fs::create_dir_all(&paths.data_dir).ok()?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(paths.log_file())
+        .ok()
+}
+
 impl RamMonitor {
     /// Creates a new RamMonitor instance with default settings
-    pub fn new() -> Self {
+    ///
+    /// # Arguments
+    /// * `paths` - Resolved locations of the config file and the data directory
+    pub fn new(paths: Paths) -> Self {
         let mut monitor = Self {
             system: System::new_all(),
             logs: VecDeque::with_capacity(LOG_CAPACITY),
+            history: VecDeque::with_capacity(HISTORY_CAPACITY),
             auto_threshold: DEFAULT_AUTO_THRESHOLD,
-            auto_action: String::from("Empty Working Sets"),
+            auto_action: ActionId::RamMap(Commands::EmptyWorkingSets),
             last_auto_execution: None,
             selected_action: 0,
             last_key_press: None,
             last_action: None,
             last_activity: Instant::now(),
             activity_state: ActivityState::Active,
+            memory_scope: MemoryScope::Host,
+            cgroup: None,
+            last_cgroup_refresh: None,
+            cgroup_tree: Vec::new(),
+            last_cgroup_tree_refresh: None,
+            view: View::Logs,
+            view_selection: 0,
+            oom_kills: 0,
+            last_oom_kill_count: None,
+            cgroup_oom_kills: HashMap::new(),
+            last_oom_check: None,
+            kernel_log: KernelLog::open(),
+            rule_last_fired: HashMap::new(),
+            process_rule_last_fired: HashMap::new(),
+            last_process_rules_check: None,
+            custom_runs: Vec::new(),
+            process_rule_runs: Vec::new(),
+            macro_run: None,
+            swap_devices: Vec::new(),
+            zswap: None,
+            last_swap_refresh: None,
+            numa_nodes: Vec::new(),
+            last_numa_refresh: None,
+            hugepages: None,
+            last_hugepages_refresh: None,
+            buddyinfo: Vec::new(),
+            pagetypeinfo: Vec::new(),
+            compaction_recommended: false,
+            last_fragmentation_refresh: None,
+            slab_caches: Vec::new(),
+            slab_baseline: HashMap::new(),
+            slab_totals: None,
+            slab_total_baseline: None,
+            slab_sort: SlabSort::Size,
+            last_slab_refresh: None,
+            page_cache_scan: None,
+            page_cache_progress: 0,
+            page_cache_files: Vec::new(),
+            page_cache_dirs_grouped: Vec::new(),
+            page_cache_grouping: CacheGrouping::Files,
+            evictions: Vec::new(),
+            prewarm: None,
+            prewarm_scheduled: None,
+            prewarm_locked: Vec::new(),
+            sysctls: read_sysctls(),
+            last_sysctl_refresh: None,
+            killer_victim: None,
+            killer_critical: false,
+            last_killer_exit: None,
+            last_killer_check: None,
+            suspended: Vec::new(),
+            suspend_exempt: HashSet::new(),
+            last_suspend_check: None,
+            notifier: None,
+            pressure_level: PressureLevel::Normal,
+            config_modified: None,
+            last_config_check: None,
+            log_file: open_log_file(&paths),
+            paths,
             config: Config::default(),
         };
 
         monitor.config = Config::load(&mut monitor);
-        monitor.auto_threshold = monitor.config.auto_threshold;
-        monitor.auto_action = monitor.config.auto_action.clone();
+        // Taken after loading, as loading may rewrite the file
+        monitor.config_modified = monitor.paths.config_modified();
+        monitor.apply_profile();
+        monitor.start_notifier();
 
         monitor
     }
@@ -46,15 +136,53 @@ impl RamMonitor {
             is_error,
         };
 
+        if let Some(file) = &mut self.log_file {
+            let level = if is_error { "ERROR" } else { "INFO" };
+            // A failing log file must not interrupt monitoring
+            let _ = writeln!(
+                file,
+                "{} {level} {}",
+                format_utc(SystemTime::now()),
+                entry.message
+            );
+            if file
+                .metadata()
+                .is_ok_and(|metadata| metadata.len() >= LOG_FILE_MAX_BYTES)
+            {
+                self.rotate_log_file();
+            }
+        }
+
         if self.logs.len() >= LOG_CAPACITY {
             self.logs.pop_back();
         }
         self.logs.push_front(entry);
     }
 
+    /// Moves the full log file to its `.1` backup, replacing the previous one, and
+    /// starts a new log file
+    fn rotate_log_file(&mut self) {
+        self.log_file = None;
+        let _ = fs::rename(self.paths.log_file(), self.paths.rotated_log_file());
+        self.log_file = open_log_file(&self.paths);
+    }
+
     /// Returns current RAM usage as (used_gb, total_gb, percentage)
+    /// In cgroup scope, usage is relative to the enclosing cgroup's memory limit
     pub fn get_ram_usage(&mut self) -> (f32, f32, f32) {
         self.system.refresh_memory();
+        self.refresh_cgroup();
+
+        if let (MemoryScope::Cgroup, Some(cgroup)) = (&self.memory_scope, &self.cgroup) {
+            let total = cgroup.limit.unwrap_or(self.system.total_memory());
+            let used = cgroup.used().min(total);
+            return (
+                bytes_to_gb(used),
+                bytes_to_gb(total),
+                calculate_percentage(used, total),
+            );
+        }
+
         let total = bytes_to_gb(self.system.total_memory());
         let used = bytes_to_gb(self.system.used_memory());
         let percentage =
@@ -78,40 +206,100 @@ impl RamMonitor {
 
     /// Renders all UI components and checks auto-execution
     pub fn ui(&mut self, f: &mut Frame<'_>) {
-        let chunks = ui::create_layout(f);
+        let chunks = ui::create_layout(f, Action::all(&self.config).len());
         let (used, total, percentage) = self.get_ram_usage();
-        let ram_gauge_color = utils::get_usage_color(percentage);
         let page_file = self.get_page_file_usage();
 
-        ui::render_ram_gauge(f, chunks[1], used, total, percentage, ram_gauge_color);
+        let ram_title = match (&self.memory_scope, &self.cgroup) {
+            (MemoryScope::Cgroup, Some(cgroup)) => format!(
+                "Cgroup Memory: {} (OOM: {}, OOM kills: {})",
+                cgroup.display_path(&self.cgroup_root()),
+                cgroup.oom,
+                cgroup.oom_kill
+            ),
+            _ => format!("RAM Usage (OOM kills: {})", self.oom_kills),
+        };
+
+        ui::render_ram_gauge(f, chunks[1], &ram_title, used, total, percentage);
         if let Some((used, total, percentage)) = page_file {
-            let page_file_color = utils::get_usage_color(percentage);
-            ui::render_page_file_gauge(f, chunks[2], used, total, percentage, page_file_color);
+            ui::render_page_file_gauge(f, chunks[2], used, total, percentage);
+        }
+        ui::render_memory_management(f, chunks[3], self.selected_action, &self.config);
+        self.record_sample(percentage);
+        ui::render_auto_execution(f, chunks[4], self);
+        match self.view {
+            View::Logs => ui::render_logs(f, chunks[5], self),
+            View::Cgroups => {
+                self.refresh_cgroup_tree();
+                ui::render_cgroup_tree(f, chunks[5], self);
+            }
+            View::Swap => {
+                self.refresh_swap_devices();
+                ui::render_swap_devices(f, chunks[5], self);
+            }
+            View::Numa => ui::render_numa_nodes(f, chunks[5], self),
+            View::HugePages => {
+                self.refresh_hugepages();
+                ui::render_hugepages(f, chunks[5], self);
+            }
+            View::Fragmentation => ui::render_fragmentation(f, chunks[5], self),
+            View::Slab => ui::render_slab_caches(f, chunks[5], self),
+            View::PageCache => ui::render_page_cache(f, chunks[5], self),
+            View::Sysctl => {
+                self.refresh_sysctls();
+                ui::render_sysctls(f, chunks[5], self);
+            }
+            View::Suspended => ui::render_suspended(f, chunks[5], self),
         }
-        ui::render_memory_management(f, chunks[3], self.selected_action);
-        ui::render_auto_execution(f, chunks[4], self.auto_threshold, &self.auto_action);
-        ui::render_logs(f, chunks[5], self);
 
+        self.poll_notifier(percentage);
         self.check_auto_execution(percentage);
+        self.check_killer();
+        self.check_suspend(percentage);
+        self.check_process_rules();
+
+        let new_oom_kills = self.check_oom_kills(percentage);
+        self.refresh_numa_nodes();
+        let node_percentages = self
+            .numa_nodes
+            .iter()
+            .map(|node| (node.id, node.percentage()))
+            .collect();
+        self.refresh_fragmentation();
+        self.refresh_slab_caches();
+        self.poll_page_cache_scan();
+        self.poll_evictions();
+        self.poll_prewarm();
+        self.poll_custom_actions();
+        self.poll_process_rule_commands();
+        self.poll_macro();
+        self.check_config_reload();
+        self.check_rules(&RuleContext {
+            percentage,
+            new_oom_kills,
+            node_percentages,
+            fragmentation_index: overall_fragmentation_index(&self.buddyinfo),
+        });
     }
 
     /// Cycles to the next available auto-execution action
     pub fn cycle_auto_action(&mut self) {
-        let current_action = match self.auto_action.as_str() {
-            "Empty Working Sets" => Commands::EmptySystemWorkingSets,
-            "Empty System Working Sets" => Commands::EmptyModifiedPageLists,
-            "Empty Modified Page Lists" => Commands::EmptyStandbyList,
-            "Empty Standby List" => Commands::EmptyPriorityZeroStandbyList,
-            _ => Commands::EmptyWorkingSets,
-        };
-        let new_action = String::from(current_action.display_name());
+        let actions = Action::auto_actions(&self.config);
+        let next = actions
+            .iter()
+            .position(|action| action.id(&self.config) == self.auto_action)
+            .map_or(0, |index| (index + 1) % actions.len());
+        let new_action = actions[next];
         self.add_log(
-            format!("Auto-execution action changed to: {new_action}"),
+            format!(
+                "Auto-execution action changed to: {}",
+                new_action.display_name(&self.config)
+            ),
             false,
         );
-        self.auto_action = new_action.clone();
-        self.config.auto_action = new_action;
-        self.handle_config_save();
+        self.auto_action = new_action.id(&self.config);
+        self.config.set_active_auto_action(self.auto_action.clone());
+        self.handle_config_save("auto_action");
     }
 
     /// Cycles auto-threshold between 20% and 95% in 5% increments
@@ -126,8 +314,40 @@ impl RamMonitor {
             false,
         );
         self.auto_threshold = new_threshold;
-        self.config.auto_threshold = new_threshold;
-        self.handle_config_save();
+        self.config.set_active_threshold(new_threshold);
+        self.handle_config_save("auto_threshold");
+    }
+
+    /// Switches the bottom panel to the next (or previous) view
+    pub fn cycle_view(&mut self, forward: bool) {
+        let index = View::ALL
+            .iter()
+            .position(|view| *view == self.view)
+            .unwrap_or(0);
+        let next = if forward {
+            (index + 1) % View::ALL.len()
+        } else {
+            (index + View::ALL.len() - 1) % View::ALL.len()
+        };
+        self.view = View::ALL[next];
+        self.view_selection = 0;
+    }
+
+    /// Moves the selection within the current view, clamped to its number of rows
+    pub fn move_view_selection(&mut self, down: bool) {
+        let len = match self.view {
+            View::Logs | View::Swap | View::Numa | View::HugePages | View::Fragmentation => 0,
+            View::Cgroups => self.cgroup_tree.len(),
+            View::Slab => self.slab_caches.len(),
+            View::PageCache => self.page_cache_entries().len(),
+            View::Sysctl => self.sysctls.len(),
+            View::Suspended => self.suspended.len(),
+        };
+        self.view_selection = if down {
+            (self.view_selection + 1).min(len.saturating_sub(1))
+        } else {
+            self.view_selection.saturating_sub(1)
+        };
     }
 
     /// Returns appropriate tick rate based on system activity state
@@ -156,15 +376,48 @@ impl RamMonitor {
         }
     }
 
-    /// Saves the current configuration to disk if there are any changes to the auto-execution settings
-    fn handle_config_save(&mut self) {
-        match self.config.save_to_disk() {
-            Ok(messages) if !messages.is_empty() => {
+    /// Saves a setting changed from the TUI to the config file
+    /// Only that setting is written, so edits to the file that weren't reloaded are kept.
+    /// The threshold and action go to the active profile when it sets them.
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `key` - The changed setting: auto_threshold, auto_action or active_profile
+    pub fn handle_config_save(&mut self, key: &str) {
+        let path = self.paths.config.clone();
+        let profile = self.config.profile().filter(|profile| match key {
+            "auto_threshold" => profile.auto_threshold.is_some(),
+            "auto_action" => profile.auto_action.is_some(),
+            _ => false,
+        });
+
+        let saved = if !path.exists() {
+            self.config.save_to_disk(&path)
+        } else if let Some(profile) = profile {
+            serde_json::to_value(profile)
+                .map_err(io::Error::from)
+                .and_then(|value| {
+                    let value = value.get(key).cloned().unwrap_or_default();
+                    Config::save_entry_field(&path, "profiles", &profile.name, key, &value)
+                })
+                .map(|()| Vec::new())
+        } else {
+            serde_json::to_value(&self.config)
+                .map_err(io::Error::from)
+                .and_then(|value| {
+                    let value = value.get(key).cloned().unwrap_or_default();
+                    Config::save_field(&path, key, &value)
+                })
+                .map(|()| Vec::new())
+        };
+        // The observer's own writes are not reloaded
+        self.config_modified = self.paths.config_modified();
+        match saved {
+            Ok(messages) => {
                 for (msg, is_error) in messages {
                     self.add_log(msg, is_error);
                 }
             }
-            Ok(_) => {}
             Err(e) => {
                 self.add_log(format!("Failed to save config: {e}"), true);
             }
@@ -0,0 +1,385 @@
+use std::{path::Path, time::Duration};
+
+use crate::components::{
+    constants::{
+        DEFAULT_PROFILE, NOTIFY_CRITICAL_MARGIN, NOTIFY_MODERATE_FORECAST_SECS,
+        NOTIFY_MODERATE_MARGIN,
+    },
+    notify_client::{PressureLevel, PressureNotification},
+    structs::RamMonitor,
+};
+
+/// Grades memory usage against the auto-execution threshold
+///
+/// # Arguments
+/// * `percentage` - Current RAM usage percentage
+/// * `threshold` - The auto-execution threshold
+/// * `eta` - Forecast time until the threshold is reached, if usage is growing
+pub fn pressure_level(percentage: f32, threshold: f32, eta: Option<Duration>) -> PressureLevel {
+    if percentage >= threshold - NOTIFY_CRITICAL_MARGIN {
+        PressureLevel::Critical
+    } else if percentage >= threshold - NOTIFY_MODERATE_MARGIN
+        || eta.is_some_and(|eta| eta.as_secs() <= NOTIFY_MODERATE_FORECAST_SECS)
+    {
+        PressureLevel::Moderate
+    } else {
+        PressureLevel::Normal
+    }
+}
+
+#[cfg(unix)]
+mod server {
+    use std::{
+        fs,
+        fs::Permissions,
+        io::{self, ErrorKind, Read, Write},
+        os::unix::{
+            fs::{FileTypeExt, PermissionsExt},
+            net::{UnixListener, UnixStream},
+        },
+        path::{Path, PathBuf},
+        time::{Duration, Instant},
+    };
+
+    use serde::Serialize;
+
+    use crate::components::{
+        constants::{
+            NOTIFY_MAX_PENDING_BYTES, NOTIFY_REGISTRATION_TIMEOUT_SECS, NOTIFY_SOCKET_MODE,
+        },
+        notify_client::{ErrorLine, PressureNotification, Registration},
+    };
+
+    /// A connected application waiting for notifications
+    struct Subscriber {
+        stream: UnixStream,
+        name: Option<String>,
+        buffer: Vec<u8>,
+        /// Bytes of sent lines the socket didn't accept yet
+        pending: Vec<u8>,
+        connected: Instant,
+    }
+
+    impl Subscriber {
+        fn new(stream: UnixStream) -> Self {
+            Self {
+                stream,
+                name: None,
+                buffer: Vec::new(),
+                pending: Vec::new(),
+                connected: Instant::now(),
+            }
+        }
+
+        /// Queues a value as one JSON line and writes as much as the socket accepts
+        fn send(&mut self, value: &impl Serialize) -> io::Result<()> {
+            let mut line = serde_json::to_vec(value)?;
+            line.push(b'\n');
+            self.pending.extend_from_slice(&line);
+            self.flush()
+        }
+
+        /// Writes queued bytes until the socket would block
+        /// Fails if the subscriber disconnected, or stopped reading while more than
+        /// NOTIFY_MAX_PENDING_BYTES piled up
+        fn flush(&mut self) -> io::Result<()> {
+            while !self.pending.is_empty() {
+                match self.stream.write(&self.pending) {
+                    Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                    Ok(written) => {
+                        self.pending.drain(..written);
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            if self.pending.len() > NOTIFY_MAX_PENDING_BYTES {
+                return Err(io::Error::new(
+                    ErrorKind::TimedOut,
+                    "stopped reading notifications",
+                ));
+            }
+            Ok(())
+        }
+    }
+
+    /// Unix domain socket that applications subscribe to for pressure notifications
+    pub struct PressureNotifier {
+        listener: UnixListener,
+        path: PathBuf,
+        subscribers: Vec<Subscriber>,
+    }
+
+    impl PressureNotifier {
+        /// Binds the notification socket, replacing a stale socket file left behind
+        /// A path that isn't a socket, or a socket another process is listening on, is left
+        /// alone and reported as an error. The socket is only accessible to its owner, as
+        /// subscribers can switch profiles
+        pub fn bind(path: &Path) -> io::Result<Self> {
+            match fs::symlink_metadata(path) {
+                Ok(metadata) if !metadata.file_type().is_socket() => {
+                    return Err(io::Error::new(
+                        ErrorKind::AlreadyExists,
+                        "path exists and is not a socket",
+                    ));
+                }
+                Ok(_) if UnixStream::connect(path).is_ok() => {
+                    return Err(io::Error::new(
+                        ErrorKind::AddrInUse,
+                        "socket is in use by another process",
+                    ));
+                }
+                Ok(_) => fs::remove_file(path)?,
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+            let listener = UnixListener::bind(path)?;
+            fs::set_permissions(path, Permissions::from_mode(NOTIFY_SOCKET_MODE))?;
+            listener.set_nonblocking(true)?;
+
+            Ok(Self {
+                listener,
+                path: path.to_path_buf(),
+                subscribers: Vec::new(),
+            })
+        }
+
+        /// Accepts new connections, reads registrations and writes what is left of earlier
+        /// notifications without blocking
+        /// Connections that don't register within NOTIFY_REGISTRATION_TIMEOUT_SECS are closed
+        ///
+        /// # Arguments
+        /// * `current` - Sent to subscribers as soon as they register
+        /// * `is_profile` - Whether a requested profile exists; a subscriber asking for
+        ///   another one gets an error line and is disconnected
+        ///
+        /// # Returns
+        /// * Log messages for newly registered, refused and dropped subscribers with whether
+        ///   they are errors, and the profiles subscribers asked to switch to
+        pub fn poll(
+            &mut self,
+            current: &PressureNotification,
+            is_profile: impl Fn(&str) -> bool,
+        ) -> (Vec<(String, bool)>, Vec<String>) {
+            let mut messages = Vec::new();
+            let mut profiles = Vec::new();
+
+            while let Ok((stream, _)) = self.listener.accept() {
+                if stream.set_nonblocking(true).is_ok() {
+                    self.subscribers.push(Subscriber::new(stream));
+                }
+            }
+
+            self.subscribers.retain_mut(|subscriber| {
+                if let Some(name) = subscriber.name.clone() {
+                    return match subscriber.flush() {
+                        Ok(()) => true,
+                        Err(e) => {
+                            messages.push((
+                                format!("Pressure subscriber disconnected: {name} ({e})"),
+                                false,
+                            ));
+                            false
+                        }
+                    };
+                }
+
+                let mut chunk = [0u8; 256];
+                match subscriber.stream.read(&mut chunk) {
+                    Ok(0) => return false,
+                    Ok(read) => subscriber.buffer.extend_from_slice(&chunk[..read]),
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+                    Err(_) => return false,
+                }
+
+                let Some(end) = subscriber.buffer.iter().position(|byte| *byte == b'\n') else {
+                    let waiting = subscriber.connected.elapsed()
+                        < Duration::from_secs(NOTIFY_REGISTRATION_TIMEOUT_SECS);
+                    if !waiting {
+                        messages.push((
+                            format!(
+                                "Closed a notify connection that didn't register within {NOTIFY_REGISTRATION_TIMEOUT_SECS}s"
+                            ),
+                            false,
+                        ));
+                    }
+                    return waiting;
+                };
+                let (name, profile) =
+                    match serde_json::from_slice::<Registration>(&subscriber.buffer[..end]) {
+                        Ok(registration) => (registration.name, registration.profile),
+                        Err(_) => ("unnamed".to_string(), None),
+                    };
+                match profile {
+                    Some(profile) if !is_profile(&profile) => {
+                        messages.push((
+                            format!("{name} requested unknown profile {profile}, refused"),
+                            true,
+                        ));
+                        let error = ErrorLine {
+                            error: format!("unknown profile {profile}"),
+                        };
+                        let _ = subscriber.send(&error);
+                        return false;
+                    }
+                    Some(profile) => {
+                        messages.push((format!("{name} requested profile {profile}"), false));
+                        profiles.push(profile);
+                    }
+                    None => {
+                        messages.push((format!("Pressure subscriber registered: {name}"), false))
+                    }
+                }
+                subscriber.name = Some(name);
+                subscriber.buffer.clear();
+                subscriber.send(current).is_ok()
+            });
+
+            (messages, profiles)
+        }
+
+        /// Sends a notification to every registered subscriber, dropping those that disconnected
+        /// or stopped reading
+        ///
+        /// # Returns
+        /// * The number of subscribers notified and log messages for dropped ones
+        pub fn broadcast(&mut self, notification: &PressureNotification) -> (usize, Vec<String>) {
+            let mut messages = Vec::new();
+            let mut notified = 0;
+
+            self.subscribers.retain_mut(|subscriber| {
+                let Some(name) = subscriber.name.clone() else {
+                    return true;
+                };
+                match subscriber.send(notification) {
+                    Ok(()) => {
+                        notified += 1;
+                        true
+                    }
+                    Err(e) => {
+                        messages.push(format!("Pressure subscriber disconnected: {name} ({e})"));
+                        false
+                    }
+                }
+            });
+
+            (notified, messages)
+        }
+    }
+
+    impl Drop for PressureNotifier {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+#[cfg(unix)]
+pub use server::PressureNotifier;
+
+/// Pressure notifications need Unix domain sockets
+#[cfg(not(unix))]
+pub struct PressureNotifier;
+
+#[cfg(not(unix))]
+impl PressureNotifier {
+    pub fn bind(_path: &Path) -> std::io::Result<Self> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "pressure notifications require Unix domain sockets",
+        ))
+    }
+
+    pub fn poll(
+        &mut self,
+        _current: &PressureNotification,
+        _is_profile: impl Fn(&str) -> bool,
+    ) -> (Vec<(String, bool)>, Vec<String>) {
+        (Vec::new(), Vec::new())
+    }
+
+    pub fn broadcast(&mut self, _notification: &PressureNotification) -> (usize, Vec<String>) {
+        (0, Vec::new())
+    }
+}
+
+impl RamMonitor {
+    /// Opens the configured notify_socket for subscribers
+    /// An already open socket is closed first, so its cleanup can't remove the new one
+    pub fn start_notifier(&mut self) {
+        self.notifier = None;
+        let Some(path) = self.config.notify_socket.clone() else {
+            return;
+        };
+
+        match PressureNotifier::bind(Path::new(&path)) {
+            Ok(notifier) => {
+                self.notifier = Some(notifier);
+                self.add_log(format!("Pressure notifications on {path}"), false);
+            }
+            Err(e) => {
+                self.add_log(format!("Failed to open notify socket {path}: {e}"), true);
+            }
+        }
+    }
+
+    /// Grades the current RAM usage against the active profile's threshold
+    fn pressure_notification(&self, percentage: f32) -> PressureNotification {
+        let threshold = self.auto_threshold;
+        PressureNotification {
+            level: pressure_level(percentage, threshold, self.forecast_time_to(threshold)),
+            percentage,
+            threshold,
+            profile: self.config.profile_name().to_string(),
+        }
+    }
+
+    /// Accepts subscribers, switches profiles they request and notifies them when the
+    /// pressure level or the profile changes
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `percentage` - Current RAM usage percentage
+    pub fn poll_notifier(&mut self, percentage: f32) {
+        let mut notification = self.pressure_notification(percentage);
+        let Some(notifier) = &mut self.notifier else {
+            return;
+        };
+        let config = &self.config;
+        let (registered, profiles) = notifier.poll(&notification, |name| {
+            name == DEFAULT_PROFILE || config.profile_index(name).is_some()
+        });
+        for (message, is_error) in registered {
+            self.add_log(message, is_error);
+        }
+        for profile in &profiles {
+            self.switch_profile(profile);
+        }
+        if !profiles.is_empty() {
+            notification = self.pressure_notification(percentage);
+        }
+
+        let changed = !profiles.is_empty() || notification.level != self.pressure_level;
+        self.pressure_level = notification.level;
+
+        let Some(notifier) = &mut self.notifier else {
+            return;
+        };
+        let mut messages = Vec::new();
+        if changed {
+            let (notified, dropped) = notifier.broadcast(&notification);
+            messages.extend(dropped);
+            if notified > 0 {
+                messages.push(format!(
+                    "Notified {notified} subscribers: {} pressure at {percentage:.1}%",
+                    notification.level.display_name()
+                ));
+            }
+        }
+
+        for message in messages {
+            self.add_log(message, false);
+        }
+    }
+}
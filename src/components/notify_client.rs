@@ -0,0 +1,162 @@
+// Client side of the memory pressure notification protocol
+//
+// The observer listens on a Unix domain socket. A subscriber connects, sends one
+// registration line and then receives one notification line whenever the pressure
+// level or the active profile changes. Every line is a JSON object terminated by '\n':
+//
+//   subscriber -> observer: {"name":"cache-service"}
+//   observer -> subscriber: {"level":"moderate","percentage":83.5,"threshold":90.0,"profile":"default"}
+//
+// A registration can also ask the observer to switch profiles, which is confirmed by a
+// notification naming the new profile. An unknown profile is answered with an error line,
+// after which the observer closes the connection:
+//
+//   subscriber -> observer: {"name":"ram-observer-cli","profile":"gaming"}
+//   observer -> subscriber: {"error":"unknown profile gaming"}
+//
+// This module only depends on std and serde so applications can copy or include it.
+
+use serde::{Deserialize, Serialize};
+
+/// How close memory usage is to the auto-execution threshold
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PressureLevel {
+    /// Usage is well below the threshold, caches can grow again
+    Normal,
+    /// Usage is approaching the threshold, drop caches that are cheap to rebuild
+    Moderate,
+    /// Usage is at or about to cross the threshold, drop everything that can be dropped
+    Critical,
+}
+
+impl PressureLevel {
+    /// Returns a human-readable name for the level
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Normal => "normal",
+            Self::Moderate => "moderate",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+/// A pressure level change sent to subscribers
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PressureNotification {
+    pub level: PressureLevel,
+    pub percentage: f32,
+    pub threshold: f32,
+    /// The active profile, whose threshold the level is graded against
+    #[serde(default)]
+    pub profile: String,
+}
+
+/// The first line a subscriber sends, naming itself for the observer's log
+#[derive(Serialize, Deserialize)]
+pub struct Registration {
+    pub name: String,
+    /// Profile the observer should switch to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+/// Sent instead of a notification when the observer refuses a registration
+#[derive(Serialize, Deserialize)]
+pub struct ErrorLine {
+    pub error: String,
+}
+
+/// A line sent by the observer
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ObserverLine {
+    Error(ErrorLine),
+    Notification(PressureNotification),
+}
+
+/// A connection to the observer's notification socket
+#[cfg(unix)]
+pub struct PressureClient {
+    reader: std::io::BufReader<std::os::unix::net::UnixStream>,
+}
+
+#[cfg(unix)]
+impl PressureClient {
+    /// Connects to the notification socket and registers under the given name
+    ///
+    /// # Arguments
+    /// * `path` - The observer's notify_socket path
+    /// * `name` - Name shown in the observer's log
+    pub fn connect(path: impl AsRef<std::path::Path>, name: &str) -> std::io::Result<Self> {
+        Self::register(
+            path,
+            Registration {
+                name: name.to_string(),
+                profile: None,
+            },
+        )
+    }
+
+    /// Connects to the notification socket and asks the observer to switch profiles
+    /// The switch is confirmed by a notification naming the profile
+    ///
+    /// # Arguments
+    /// * `path` - The observer's notify_socket path
+    /// * `name` - Name shown in the observer's log
+    /// * `profile` - The profile to switch to
+    pub fn request_profile(
+        path: impl AsRef<std::path::Path>,
+        name: &str,
+        profile: &str,
+    ) -> std::io::Result<Self> {
+        Self::register(
+            path,
+            Registration {
+                name: name.to_string(),
+                profile: Some(profile.to_string()),
+            },
+        )
+    }
+
+    /// Connects and sends the registration line
+    fn register(
+        path: impl AsRef<std::path::Path>,
+        registration: Registration,
+    ) -> std::io::Result<Self> {
+        use std::io::Write;
+
+        let mut stream = std::os::unix::net::UnixStream::connect(path)?;
+        let mut line = serde_json::to_string(&registration)?;
+        line.push('\n');
+        stream.write_all(line.as_bytes())?;
+
+        Ok(Self {
+            reader: std::io::BufReader::new(stream),
+        })
+    }
+
+    /// Limits how long recv blocks, after which it fails with a WouldBlock or TimedOut error
+    pub fn set_timeout(&self, timeout: Option<std::time::Duration>) -> std::io::Result<()> {
+        self.reader.get_ref().set_read_timeout(timeout)
+    }
+
+    /// Blocks until the next notification arrives
+    ///
+    /// # Returns
+    /// * `Ok(Some(PressureNotification))` for each level change
+    /// * `Ok(None)` once the observer closes the connection
+    /// * `Err` with the observer's message if it refused the registration
+    pub fn recv(&mut self) -> std::io::Result<Option<PressureNotification>> {
+        use std::io::BufRead;
+
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        match serde_json::from_str(&line)? {
+            ObserverLine::Notification(notification) => Ok(Some(notification)),
+            ObserverLine::Error(ErrorLine { error }) => Err(std::io::Error::other(error)),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::{fs, time::Instant};
+
+use crate::components::{
+    constants::NUMA_REFRESH_MS,
+    procfs::find_value,
+    structs::RamMonitor,
+    utils::{calculate_percentage, is_due},
+};
+
+/// Memory counters of a single NUMA node, from /sys/devices/system/node/nodeN/meminfo
+pub struct NumaNode {
+    pub id: usize,
+    pub total: u64,
+    pub free: u64,
+    pub file: u64,
+    pub anon: u64,
+}
+
+impl NumaNode {
+    /// Memory in use on the node, excluding page cache
+    pub fn used(&self) -> u64 {
+        self.total
+            .saturating_sub(self.free)
+            .saturating_sub(self.file)
+    }
+
+    /// Node usage as a percentage of its total memory
+    pub fn percentage(&self) -> f32 {
+        calculate_percentage(self.used(), self.total)
+    }
+}
+
+/// Parses a node meminfo file, whose lines look like "Node 0 MemTotal: 16314128 kB"
+fn parse_node_meminfo(id: usize, contents: &str) -> Option<NumaNode> {
+    let prefix = format!("Node {id} ");
+    let stripped: String = contents
+        .lines()
+        .map(|line| line.strip_prefix(&prefix).unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let kb = |key: &str| find_value(&stripped, key).map(|value| value * 1024);
+
+    Some(NumaNode {
+        id,
+        total: kb("MemTotal")?,
+        free: kb("MemFree")?,
+        file: kb("FilePages").unwrap_or(0),
+        anon: kb("AnonPages").unwrap_or(0),
+    })
+}
+
+/// Reads every NUMA node's meminfo, ordered by node id
+pub fn read_numa_nodes() -> Vec<NumaNode> {
+    let Ok(entries) = fs::read_dir("/sys/devices/system/node") else {
+        return Vec::new();
+    };
+
+    let mut nodes: Vec<NumaNode> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let id = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("node")?
+                .parse()
+                .ok()?;
+            let contents = fs::read_to_string(entry.path().join("meminfo")).ok()?;
+            parse_node_meminfo(id, &contents)
+        })
+        .collect();
+    nodes.sort_by_key(|node| node.id);
+    nodes
+}
+
+impl RamMonitor {
+    /// Re-reads per-node memory counters at most once per NUMA_REFRESH_MS
+    pub fn refresh_numa_nodes(&mut self) {
+        if !is_due(self.last_numa_refresh, NUMA_REFRESH_MS) {
+            return;
+        }
+
+        self.numa_nodes = read_numa_nodes();
+        self.last_numa_refresh = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Captured from /sys/devices/system/node/node0/meminfo
+    const NODE_MEMINFO: &str = "\
+Node 0 MemTotal:        6158152 kB
+Node 0 MemFree:         3433672 kB
+Node 0 MemUsed:         2724480 kB
+Node 0 SwapCached:            0 kB
+Node 0 Active:          1200036 kB
+Node 0 Inactive:        1155372 kB
+Node 0 FilePages:       2180260 kB
+Node 0 AnonPages:        186372 kB
+";
+
+    #[test]
+    fn parses_node_meminfo() {
+        let node = parse_node_meminfo(0, NODE_MEMINFO).unwrap();
+        assert_eq!(node.id, 0);
+        assert_eq!(node.total, 6158152 * 1024);
+        assert_eq!(node.free, 3433672 * 1024);
+        assert_eq!(node.file, 2180260 * 1024);
+        assert_eq!(node.anon, 186372 * 1024);
+        assert_eq!(node.used(), (6158152 - 3433672 - 2180260) * 1024);
+    }
+
+    #[test]
+    fn node_meminfo_needs_totals() {
+        assert!(parse_node_meminfo(0, "Node 0 FilePages: 1 kB\n").is_none());
+        // Lines of another node don't count
+        assert!(parse_node_meminfo(1, NODE_MEMINFO).is_none());
+    }
+}
@@ -0,0 +1,175 @@
+use std::{collections::HashMap, fs, path::PathBuf, time::Instant};
+
+use crate::components::{
+    cgroup::{CgroupNode, CgroupStats},
+    constants::OOM_CHECK_MS,
+    procfs::find_value,
+    structs::RamMonitor,
+    utils::is_due,
+};
+
+/// Reads the system-wide OOM kill counter from /proc/vmstat
+fn read_oom_kill_count() -> Option<u64> {
+    find_value(&fs::read_to_string("/proc/vmstat").ok()?, "oom_kill")
+}
+
+/// Snapshots the oom_kill counter of every cgroup in the hierarchy
+fn read_cgroup_oom_kills(tree: &[CgroupNode]) -> HashMap<PathBuf, u64> {
+    tree.iter()
+        .map(|node| (node.stats.path.clone(), node.stats.oom_kill))
+        .collect()
+}
+
+/// The kernel log, read from where it ended when the observer started
+/// Kept open so every read only returns records logged since the previous one
+pub struct KernelLog {
+    #[cfg(unix)]
+    file: fs::File,
+}
+
+impl KernelLog {
+    /// Opens /dev/kmsg positioned at its end, so earlier kills aren't attributed to new ones
+    ///
+    /// # Returns
+    /// * `None` if /dev/kmsg isn't readable (unprivileged or non-Linux)
+    #[cfg(unix)]
+    pub fn open() -> Option<Self> {
+        use std::{
+            fs::OpenOptions,
+            io::{Seek, SeekFrom},
+            os::unix::fs::OpenOptionsExt,
+        };
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open("/dev/kmsg")
+            .ok()?;
+        file.seek(SeekFrom::End(0)).ok()?;
+        Some(Self { file })
+    }
+
+    #[cfg(not(unix))]
+    pub fn open() -> Option<Self> {
+        None
+    }
+
+    /// Extracts the "Killed process <pid> (<name>)" entries logged since the last read
+    #[cfg(unix)]
+    pub fn read_victims(&mut self) -> Vec<String> {
+        use std::io::{ErrorKind, Read};
+
+        let mut victims = Vec::new();
+        let mut record = [0u8; 8192];
+        loop {
+            match self.file.read(&mut record) {
+                Ok(0) => break,
+                Ok(len) => {
+                    let line = String::from_utf8_lossy(&record[..len]);
+                    if let Some((_, killed)) = line.split_once("Killed process ") {
+                        victims.extend(
+                            killed
+                                .split_once(')')
+                                .map(|(process, _)| format!("{process})")),
+                        );
+                    }
+                }
+                // Records were overwritten before they were read, continue with the next one
+                Err(e) if e.raw_os_error() == Some(libc::EPIPE) => continue,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(_) => break,
+            }
+        }
+        victims
+    }
+
+    #[cfg(not(unix))]
+    pub fn read_victims(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+impl RamMonitor {
+    /// Checks /proc/vmstat and cgroup memory.events for new OOM kills
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `percentage` - Current RAM usage percentage, recorded with each kill
+    ///
+    /// # Returns
+    /// * The number of OOM kills since the last check
+    pub fn check_oom_kills(&mut self, percentage: f32) -> u64 {
+        if !is_due(self.last_oom_check, OOM_CHECK_MS) {
+            return 0;
+        }
+        self.last_oom_check = Some(Instant::now());
+
+        let Some(count) = read_oom_kill_count() else {
+            return 0;
+        };
+        let root = self.cgroup_root();
+        let Some(previous) = self.last_oom_kill_count.replace(count) else {
+            // First check, record the baselines without reporting old kills
+            self.refresh_cgroup_tree();
+            self.cgroup_oom_kills = read_cgroup_oom_kills(&self.cgroup_tree);
+            return 0;
+        };
+
+        let new_kills = count.saturating_sub(previous);
+        if new_kills == 0 {
+            return 0;
+        }
+        self.oom_kills += new_kills;
+
+        // Find which groups the kills happened in
+        self.last_cgroup_tree_refresh = None;
+        self.refresh_cgroup_tree();
+        let killed_in: Vec<&CgroupStats> = self
+            .cgroup_tree
+            .iter()
+            .map(|node| &node.stats)
+            .filter(|stats| {
+                stats.oom_kill > self.cgroup_oom_kills.get(&stats.path).copied().unwrap_or(0)
+            })
+            .collect();
+        // memory.events is hierarchical, so only report the innermost groups
+        let groups: Vec<String> = killed_in
+            .iter()
+            .filter(|stats| {
+                !killed_in
+                    .iter()
+                    .any(|other| other.path != stats.path && other.path.starts_with(&stats.path))
+            })
+            .map(|stats| stats.display_path(&root))
+            .collect();
+        self.cgroup_oom_kills = read_cgroup_oom_kills(&self.cgroup_tree);
+
+        let swap = self
+            .get_page_file_usage()
+            .map(|(_, _, swap)| format!(", swap {swap:.1}%"))
+            .unwrap_or_default();
+        let victims = self
+            .kernel_log
+            .as_mut()
+            .map(KernelLog::read_victims)
+            .unwrap_or_default();
+        let victim = match victims.len() {
+            0 => String::new(),
+            1 => format!(", victim: {}", victims[0]),
+            _ => format!(", victims: {}", victims.join(" ")),
+        };
+        let groups = if groups.is_empty() {
+            String::new()
+        } else {
+            format!(", cgroups: {}", groups.join(" "))
+        };
+        self.add_log(
+            format!(
+                "OOM kill detected ({new_kills} new) at RAM {percentage:.1}%{swap}{victim}{groups}"
+            ),
+            true,
+        );
+
+        new_kills
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::components::{
+    constants::{PAGE_CACHE_MAX_FILES, PAGE_CACHE_PROGRESS_INTERVAL},
+    procfs::page_size,
+    structs::RamMonitor,
+    utils::format_bytes,
+};
+
+/// Page cache residency of a single file, or of all files directly inside a directory
+pub struct CachedEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    pub cached: u64,
+}
+
+/// Messages sent from the page cache scan worker to the UI thread
+pub enum ScanMessage {
+    Progress(usize),
+    Done(Vec<CachedEntry>),
+}
+
+/// Whether the page cache view lists files or their directories
+#[derive(Clone, Copy)]
+pub enum CacheGrouping {
+    Files,
+    Directories,
+}
+
+/// Calls `visit` for every regular file below the given roots, without following symlinks
+///
+/// # Arguments
+/// * `roots` - Files or directories to walk
+/// * `limit` - Maximum number of files to visit
+/// * `visit` - Called with each file path; returning false stops the walk
+pub fn walk_files(roots: &[PathBuf], limit: usize, mut visit: impl FnMut(&Path) -> bool) {
+    let mut pending: Vec<PathBuf> = roots.to_vec();
+    let mut visited = 0;
+
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            if let Ok(entries) = fs::read_dir(&path) {
+                pending.extend(entries.filter_map(Result::ok).map(|entry| entry.path()));
+            }
+        } else if metadata.is_file() {
+            visited += 1;
+            if visited > limit || !visit(&path) {
+                return;
+            }
+        }
+    }
+}
+
+/// Counts how many bytes of a file are resident in the page cache using mincore
+#[cfg(unix)]
+pub fn cached_bytes(file: &File, len: u64) -> io::Result<u64> {
+    use std::{os::fd::AsRawFd, ptr};
+
+    if len == 0 {
+        return Ok(0);
+    }
+    let page_size = page_size();
+    let len = usize::try_from(len).map_err(io::Error::other)?;
+
+    // SAFETY: maps the file read-only and shared; the mapping is never dereferenced
+    // and is unmapped before returning
+    let addr = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            len,
+            libc::PROT_READ,
+            libc::MAP_SHARED,
+            file.as_raw_fd(),
+            0,
+        )
+    };
+    if addr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut residency = vec![0u8; len.div_ceil(page_size as usize)];
+    // SAFETY: `addr` is a valid mapping of `len` bytes and `residency` holds one byte per page
+    let result = unsafe { libc::mincore(addr, len, residency.as_mut_ptr().cast()) };
+    let error = io::Error::last_os_error();
+    // SAFETY: `addr` and `len` describe the mapping created above
+    unsafe { libc::munmap(addr, len) };
+
+    if result != 0 {
+        return Err(error);
+    }
+    // The last page may extend past the end of the file
+    let pages = residency.iter().filter(|page| *page & 1 == 1).count() as u64;
+    Ok((pages * page_size).min(len as u64))
+}
+
+#[cfg(not(unix))]
+pub fn cached_bytes(_file: &File, _len: u64) -> io::Result<u64> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "page cache inspection requires mincore",
+    ))
+}
+
+/// Scans every file below the given directories and reports cached bytes per file
+///
+/// # Returns
+/// * Receiver for progress updates and the final, size-sorted result
+fn spawn_scan(roots: Vec<PathBuf>) -> Receiver<ScanMessage> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut entries = Vec::new();
+        let mut scanned = 0;
+
+        walk_files(&roots, PAGE_CACHE_MAX_FILES, |path| {
+            scanned += 1;
+            if scanned % PAGE_CACHE_PROGRESS_INTERVAL == 0 {
+                let _ = sender.send(ScanMessage::Progress(scanned));
+            }
+
+            let cached = File::open(path).and_then(|file| {
+                let size = file.metadata()?.len();
+                Ok((size, cached_bytes(&file, size)?))
+            });
+            if let Ok((size, cached)) = cached
+                && cached > 0
+            {
+                entries.push(CachedEntry {
+                    path: path.to_path_buf(),
+                    size,
+                    cached,
+                });
+            }
+            true
+        });
+
+        entries.sort_by_key(|entry| Reverse(entry.cached));
+        let _ = sender.send(ScanMessage::Done(entries));
+    });
+
+    receiver
+}
+
+/// Sums cached bytes of files by their parent directory
+pub fn group_by_directory(files: &[CachedEntry]) -> Vec<CachedEntry> {
+    let mut directories: HashMap<&Path, (u64, u64)> = HashMap::new();
+    for file in files {
+        if let Some(parent) = file.path.parent() {
+            let totals = directories.entry(parent).or_default();
+            totals.0 += file.size;
+            totals.1 += file.cached;
+        }
+    }
+
+    let mut grouped: Vec<CachedEntry> = directories
+        .into_iter()
+        .map(|(path, (size, cached))| CachedEntry {
+            path: path.to_path_buf(),
+            size,
+            cached,
+        })
+        .collect();
+    grouped.sort_by_key(|entry| Reverse(entry.cached));
+    grouped
+}
+
+impl RamMonitor {
+    /// Starts a background scan of the configured page cache directories
+    pub fn start_page_cache_scan(&mut self) {
+        if self.page_cache_scan.is_some() {
+            return;
+        }
+        if self.config.page_cache_dirs.is_empty() {
+            self.add_log(
+                "No page_cache_dirs configured, nothing to scan".to_string(),
+                true,
+            );
+            return;
+        }
+
+        let roots = self
+            .config
+            .page_cache_dirs
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        self.add_log("Scanning page cache...".to_string(), false);
+        self.page_cache_progress = 0;
+        self.page_cache_scan = Some(spawn_scan(roots));
+    }
+
+    /// Collects progress and results from a running page cache scan
+    pub fn poll_page_cache_scan(&mut self) {
+        let Some(receiver) = &self.page_cache_scan else {
+            return;
+        };
+
+        loop {
+            match receiver.try_recv() {
+                Ok(ScanMessage::Progress(scanned)) => self.page_cache_progress = scanned,
+                Ok(ScanMessage::Done(entries)) => {
+                    let total: u64 = entries.iter().map(|entry| entry.cached).sum();
+                    self.page_cache_dirs_grouped = group_by_directory(&entries);
+                    self.page_cache_files = entries;
+                    self.page_cache_scan = None;
+                    self.add_log(
+                        format!(
+                            "Page cache scan finished: {} cached files, {}",
+                            self.page_cache_files.len(),
+                            format_bytes(total)
+                        ),
+                        false,
+                    );
+                    return;
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.page_cache_scan = None;
+                    self.add_log("Page cache scan stopped unexpectedly".to_string(), true);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Switches the page cache view between files and directories
+    pub fn toggle_page_cache_grouping(&mut self) {
+        self.page_cache_grouping = match self.page_cache_grouping {
+            CacheGrouping::Files => CacheGrouping::Directories,
+            CacheGrouping::Directories => CacheGrouping::Files,
+        };
+        self.view_selection = 0;
+    }
+
+    /// Returns the entries shown in the page cache view for the current grouping
+    pub fn page_cache_entries(&self) -> &[CachedEntry] {
+        match self.page_cache_grouping {
+            CacheGrouping::Files => &self.page_cache_files,
+            CacheGrouping::Directories => &self.page_cache_dirs_grouped,
+        }
+    }
+}
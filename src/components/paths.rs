@@ -0,0 +1,143 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::components::constants::{
+    APP_DIR_NAME, CONFIG_ENV_VAR, CONFIG_FILE, CONFIG_FILE_TOML, LOG_FILE, RAMMAP_EXECUTABLE,
+};
+
+/// Where the config file was found
+#[derive(Clone, Copy, PartialEq)]
+pub enum ConfigOrigin {
+    /// Given with --config
+    Flag,
+    /// Given with the RAM_OBSERVER_CONFIG environment variable
+    Environment,
+    /// Found in the working directory, which makes the observer portable
+    WorkingDirectory,
+    /// Found in, or to be created in, the platform's config directory
+    ConfigDir,
+}
+
+impl ConfigOrigin {
+    /// Returns a human-readable name for the origin
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Flag => "--config",
+            Self::Environment => CONFIG_ENV_VAR,
+            Self::WorkingDirectory => "portable",
+            Self::ConfigDir => "config dir",
+        }
+    }
+}
+
+/// Resolved locations of the config file and of the files the observer writes
+#[derive(Clone)]
+pub struct Paths {
+    pub config: PathBuf,
+    pub origin: ConfigOrigin,
+    /// Directory for the log file and downloaded tools
+    pub data_dir: PathBuf,
+}
+
+impl Paths {
+    /// Resolves the config file, in order of precedence:
+    /// 1. The path given with --config
+    /// 2. The RAM_OBSERVER_CONFIG environment variable
+    /// 3. A config file in the working directory (portable mode)
+    /// 4. The platform config directory (XDG on Linux, AppData on Windows),
+    ///    where the config is created if it doesn't exist yet
+    ///
+    /// In portable mode data is kept in the working directory, otherwise in the
+    /// platform data directory
+    pub fn resolve(explicit: Option<&str>) -> Self {
+        let from_env = env::var(CONFIG_ENV_VAR)
+            .ok()
+            .filter(|path| !path.is_empty());
+        let (config, origin) = if let Some(path) = explicit {
+            (PathBuf::from(path), ConfigOrigin::Flag)
+        } else if let Some(path) = from_env {
+            (PathBuf::from(path), ConfigOrigin::Environment)
+        } else if let Some(path) = find_config(Path::new("")) {
+            (path, ConfigOrigin::WorkingDirectory)
+        } else if let Some(dir) = config_dir() {
+            let dir = dir.join(APP_DIR_NAME);
+            let path = find_config(&dir).unwrap_or_else(|| dir.join(CONFIG_FILE));
+            (path, ConfigOrigin::ConfigDir)
+        } else {
+            (PathBuf::from(CONFIG_FILE), ConfigOrigin::WorkingDirectory)
+        };
+
+        let data_dir = match origin {
+            ConfigOrigin::WorkingDirectory => None,
+            _ => data_dir().map(|dir| dir.join(APP_DIR_NAME)),
+        };
+
+        Self {
+            config,
+            origin,
+            data_dir: data_dir.unwrap_or_default(),
+        }
+    }
+
+    /// Returns when the config file was last modified, or None if it doesn't exist
+    pub fn config_modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.config)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    /// Returns the file the log is appended to
+    pub fn log_file(&self) -> PathBuf {
+        self.data_dir.join(LOG_FILE)
+    }
+
+    /// Returns the previous log file, kept once the log file reached LOG_FILE_MAX_BYTES
+    pub fn rotated_log_file(&self) -> PathBuf {
+        self.data_dir.join(format!("{LOG_FILE}.1"))
+    }
+
+    /// Returns where RAMMap is downloaded to and run from
+    pub fn rammap(&self) -> PathBuf {
+        self.data_dir.join(RAMMAP_EXECUTABLE)
+    }
+}
+
+/// Returns the config file in a directory, preferring TOML over JSON
+fn find_config(dir: &Path) -> Option<PathBuf> {
+    [CONFIG_FILE_TOML, CONFIG_FILE]
+        .into_iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+}
+
+/// Reads a directory from an environment variable, ignoring relative paths
+fn env_dir(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+/// Returns the platform's per-user config directory
+fn config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        env_dir("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        env_dir("XDG_CONFIG_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+    }
+}
+
+/// Returns the platform's per-user data directory
+fn data_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        env_dir("LOCALAPPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        env_dir("XDG_DATA_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".local/share")))
+    }
+}
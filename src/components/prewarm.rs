@@ -0,0 +1,228 @@
+use std::{
+    fs::File,
+    io,
+    path::PathBuf,
+    sync::mpsc::{self, TryRecvError},
+    thread,
+    time::Instant,
+};
+
+use crate::components::{
+    constants::{PAGE_CACHE_MAX_FILES, PREWARM_DELAY_MS},
+    page_cache::{cached_bytes, walk_files},
+    structs::RamMonitor,
+    utils::{format_bytes, is_due},
+};
+
+/// A file mapping whose pages are pinned in memory with mlock
+/// The pages stay locked until the mapping is dropped
+#[cfg_attr(not(unix), allow(dead_code))]
+pub struct LockedMapping {
+    addr: usize,
+    len: usize,
+}
+
+impl LockedMapping {
+    /// Maps a file and locks all of its pages into memory, reading them in if necessary
+    #[cfg(unix)]
+    fn lock(file: &File, len: u64) -> io::Result<Self> {
+        use std::{os::fd::AsRawFd, ptr};
+
+        let len = usize::try_from(len).map_err(io::Error::other)?;
+        // SAFETY: maps the file read-only and shared; the mapping is only unmapped on drop
+        let addr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if addr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        // SAFETY: `addr` is a valid mapping of `len` bytes
+        if unsafe { libc::mlock(addr, len) } != 0 {
+            let error = io::Error::last_os_error();
+            // SAFETY: `addr` and `len` describe the mapping created above
+            unsafe { libc::munmap(addr, len) };
+            return Err(error);
+        }
+
+        Ok(Self {
+            addr: addr as usize,
+            len,
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn lock(_file: &File, _len: u64) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "page locking requires mlock",
+        ))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for LockedMapping {
+    fn drop(&mut self) {
+        // SAFETY: `addr` and `len` describe a mapping created in `lock` that is unmapped only here
+        unsafe { libc::munmap(self.addr as *mut libc::c_void, self.len) };
+    }
+}
+
+/// Outcome of pre-warming the page cache
+#[derive(Default)]
+pub struct PrewarmResult {
+    pub files: usize,
+    pub warmed: u64,
+    pub locked: u64,
+    pub errors: usize,
+    pub lock_failures: usize,
+    pub mappings: Vec<LockedMapping>,
+}
+
+/// Reads a file into the page cache, or locks it if it fits in the remaining budget
+/// Files that can't be locked, e.g. because of RLIMIT_MEMLOCK, are only read
+///
+/// # Returns
+/// * Bytes newly brought into the page cache, and the locking outcome if locking was attempted
+fn warm_file(
+    file: &mut File,
+    lock_budget: &mut u64,
+) -> io::Result<(u64, Option<io::Result<LockedMapping>>)> {
+    let size = file.metadata()?.len();
+    let before = cached_bytes(file, size).unwrap_or(0);
+
+    let lock = (size > 0 && size <= *lock_budget).then(|| LockedMapping::lock(file, size));
+    match &lock {
+        Some(Ok(_)) => *lock_budget -= size,
+        _ => {
+            io::copy(file, &mut io::sink())?;
+        }
+    }
+
+    let after = cached_bytes(file, size).unwrap_or(size);
+    Ok((after.saturating_sub(before), lock))
+}
+
+/// Reads every file below the given roots into the page cache, locking files
+/// with mlock until the byte budget is used up
+pub fn prewarm(roots: &[PathBuf], lock_budget: u64) -> PrewarmResult {
+    let mut result = PrewarmResult::default();
+    let mut lock_budget = lock_budget;
+
+    walk_files(roots, PAGE_CACHE_MAX_FILES, |path| {
+        match File::open(path).and_then(|mut file| warm_file(&mut file, &mut lock_budget)) {
+            Ok((warmed, mapping)) => {
+                result.files += 1;
+                result.warmed += warmed;
+                match mapping {
+                    Some(Ok(mapping)) => {
+                        result.locked += mapping.len as u64;
+                        result.mappings.push(mapping);
+                    }
+                    Some(Err(_)) => result.lock_failures += 1,
+                    None => {}
+                }
+            }
+            Err(_) => result.errors += 1,
+        }
+        true
+    });
+
+    result
+}
+
+impl RamMonitor {
+    /// Schedules a pre-warm of the configured files PREWARM_DELAY_MS from now,
+    /// giving a purge time to finish first
+    pub fn schedule_prewarm(&mut self) {
+        if !self.config.prewarm_paths.is_empty() {
+            self.prewarm_scheduled = Some(Instant::now());
+        }
+    }
+
+    /// Reads the configured prewarm_paths into the page cache in a background worker
+    /// Previously locked files are unlocked first so the lock budget applies again
+    pub fn start_prewarm(&mut self) {
+        self.prewarm_scheduled = None;
+        if self.prewarm.is_some() {
+            return;
+        }
+        if self.config.prewarm_paths.is_empty() {
+            self.add_log(
+                "No prewarm_paths configured, nothing to pre-warm".to_string(),
+                true,
+            );
+            return;
+        }
+
+        let roots: Vec<PathBuf> = self
+            .config
+            .prewarm_paths
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        let lock_budget = self.config.prewarm_lock_bytes.unwrap_or(0);
+        self.prewarm_locked.clear();
+
+        self.add_log("Pre-warming page cache...".to_string(), false);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(prewarm(&roots, lock_budget));
+        });
+        self.prewarm = Some(receiver);
+    }
+
+    /// Starts a scheduled pre-warm once it is due and collects the result of a running one
+    pub fn poll_prewarm(&mut self) {
+        if self.prewarm_scheduled.is_some() && is_due(self.prewarm_scheduled, PREWARM_DELAY_MS) {
+            self.start_prewarm();
+        }
+
+        let Some(receiver) = &self.prewarm else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(result) => {
+                self.prewarm = None;
+                let locked = match result.locked {
+                    0 => String::new(),
+                    locked => format!(", {} locked", format_bytes(locked)),
+                };
+                let errors = match result.errors {
+                    0 => String::new(),
+                    errors => format!(", {errors} files failed"),
+                };
+                if result.lock_failures > 0 {
+                    self.add_log(
+                        format!(
+                            "{} files could not be locked, check RLIMIT_MEMLOCK",
+                            result.lock_failures
+                        ),
+                        true,
+                    );
+                }
+                self.add_log(
+                    format!(
+                        "Pre-warmed {} files: {} warmed{locked}{errors}",
+                        result.files,
+                        format_bytes(result.warmed)
+                    ),
+                    result.files == 0 && result.errors > 0,
+                );
+                self.prewarm_locked = result.mappings;
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => {
+                self.prewarm = None;
+                self.add_log("Pre-warm stopped unexpectedly".to_string(), true);
+            }
+        }
+    }
+}
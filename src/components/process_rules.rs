@@ -0,0 +1,264 @@
+use std::{process::Command, time::Instant};
+
+use glob::Pattern;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, UpdateKind};
+
+use crate::components::{
+    cgroup::process_cgroup,
+    constants::{AUTO_EXECUTION_COOLDOWN_SECS, CGROUP_RECLAIM_PERCENT, PROCESS_RULES_CHECK_MS},
+    custom_actions::describe_status,
+    memory_management::CgroupCommands,
+    procfs::find_value,
+    structs::{ProcessAction, ProcessMatcher, ProcessRule, RamMonitor},
+    utils::{format_bytes, is_due},
+};
+
+/// Parses a signal name such as "SIGUSR1" or "usr1"
+pub fn parse_signal(name: &str) -> Option<Signal> {
+    let upper = name.to_ascii_uppercase();
+    match upper.strip_prefix("SIG").unwrap_or(&upper) {
+        "HUP" => Some(Signal::Hangup),
+        "INT" => Some(Signal::Interrupt),
+        "QUIT" => Some(Signal::Quit),
+        "KILL" => Some(Signal::Kill),
+        "USR1" => Some(Signal::User1),
+        "USR2" => Some(Signal::User2),
+        "TERM" => Some(Signal::Term),
+        "CONT" => Some(Signal::Continue),
+        "STOP" => Some(Signal::Stop),
+        _ => None,
+    }
+}
+
+/// Reads a process's swapped-out memory from the VmSwap line of /proc/<pid>/status
+fn read_process_swap(pid: Pid) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    find_value(&status, "VmSwap").map(|kb| kb * 1024)
+}
+
+/// Returns true when the value matches the glob pattern, or when there is no pattern
+fn glob_matches(pattern: &Option<String>, value: Option<&str>) -> bool {
+    match pattern {
+        None => true,
+        Some(pattern) => value
+            .is_some_and(|value| Pattern::new(pattern).is_ok_and(|pattern| pattern.matches(value))),
+    }
+}
+
+impl ProcessMatcher {
+    /// Returns true when at least one criterion is set, so a rule can't match everything
+    pub fn is_set(&self) -> bool {
+        self.name.is_some() || self.cmdline.is_some() || self.cgroup.is_some()
+    }
+
+    /// Returns the glob patterns of the matcher
+    pub fn patterns(&self) -> impl Iterator<Item = &String> {
+        [&self.name, &self.cmdline, &self.cgroup]
+            .into_iter()
+            .flatten()
+    }
+}
+
+impl ProcessRule {
+    /// Returns a human-readable description of the rule for logging
+    pub fn describe(&self) -> String {
+        let criteria = [
+            ("name", &self.matcher.name),
+            ("cmdline", &self.matcher.cmdline),
+            ("cgroup", &self.matcher.cgroup),
+        ]
+        .into_iter()
+        .filter_map(|(key, pattern)| pattern.as_ref().map(|pattern| format!("{key}={pattern}")));
+        let limits = [("RSS", self.rss_bytes), ("swap", self.swap_bytes)]
+            .into_iter()
+            .filter_map(|(key, limit)| limit.map(|limit| format!("{key}>{}", format_bytes(limit))));
+        criteria.chain(limits).collect::<Vec<_>>().join(" ")
+    }
+}
+
+impl ProcessAction {
+    /// Returns a human-readable description of the action for logging
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Signal { signal } => format!("sending {signal}"),
+            Self::Command { command } => format!("running {command}"),
+            Self::Trim => String::from("reclaiming its cgroup"),
+        }
+    }
+}
+
+/// A process whose memory exceeds a rule's thresholds
+struct Offender {
+    pid: Pid,
+    name: String,
+    rss: u64,
+}
+
+impl RamMonitor {
+    /// Finds processes that match a rule and exceed its RSS or swap threshold
+    fn find_offenders(&self, rule: &ProcessRule) -> Vec<Offender> {
+        self.system
+            .processes()
+            .iter()
+            .filter(|(_, process)| process.thread_kind().is_none())
+            .filter_map(|(pid, process)| {
+                let name = process.name().to_string_lossy().to_string();
+                let cmdline = process
+                    .cmd()
+                    .iter()
+                    .map(|arg| arg.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let cgroup = rule
+                    .matcher
+                    .cgroup
+                    .as_ref()
+                    .and_then(|_| process_cgroup(&pid.to_string()));
+                if !glob_matches(&rule.matcher.name, Some(&name))
+                    || !glob_matches(&rule.matcher.cmdline, Some(&cmdline))
+                    || !glob_matches(&rule.matcher.cgroup, cgroup.as_deref())
+                {
+                    return None;
+                }
+
+                let rss = process.memory();
+                let over_rss = rule.rss_bytes.is_some_and(|limit| rss > limit);
+                let over_swap = rule
+                    .swap_bytes
+                    .is_some_and(|limit| read_process_swap(*pid).is_some_and(|swap| swap > limit));
+                (over_rss || over_swap).then_some(Offender {
+                    pid: *pid,
+                    name,
+                    rss,
+                })
+            })
+            .collect()
+    }
+
+    /// Runs a process rule's action against one process
+    fn run_process_action(&mut self, action: &ProcessAction, offender: &Offender) {
+        let Offender { pid, name, rss } = offender;
+        match action {
+            ProcessAction::Signal { signal } => {
+                let sent = parse_signal(signal).and_then(|signal| {
+                    self.system
+                        .process(*pid)
+                        .and_then(|process| process.kill_with(signal))
+                });
+                if sent != Some(true) {
+                    self.add_log(
+                        format!("Failed to send {signal} to {name} (pid {pid})"),
+                        true,
+                    );
+                }
+            }
+            ProcessAction::Command { command } => {
+                let (shell, flag) = if cfg!(windows) {
+                    ("cmd", "/C")
+                } else {
+                    ("sh", "-c")
+                };
+                let spawned = Command::new(shell)
+                    .arg(flag)
+                    .arg(command)
+                    .env("RAM_OBSERVER_PID", pid.to_string())
+                    .env("RAM_OBSERVER_NAME", name)
+                    .env("RAM_OBSERVER_RSS", rss.to_string())
+                    .spawn();
+                match spawned {
+                    Ok(child) => self.process_rule_runs.push((command.clone(), child)),
+                    Err(e) => self.add_log(format!("Failed to run {command}: {e}"), true),
+                }
+            }
+            ProcessAction::Trim => match process_cgroup(&pid.to_string()) {
+                Some(group) => {
+                    let action = CgroupCommands::Reclaim {
+                        group: self.cgroup_root().join(group),
+                        bytes: rss / 100 * CGROUP_RECLAIM_PERCENT,
+                    };
+                    self.run_cgroup_command(action);
+                }
+                None => {
+                    self.add_log(format!("No cgroup found for {name} (pid {pid})"), true);
+                }
+            },
+        }
+    }
+
+    /// Evaluates the active profile's process rules at most once per PROCESS_RULES_CHECK_MS
+    ///
+    /// # Behavior
+    /// * A rule triggers for every matching process above its RSS or swap threshold
+    /// * Each rule has its own cooldown (defaults to AUTO_EXECUTION_COOLDOWN_SECS)
+    pub fn check_process_rules(&mut self) {
+        if self.config.active_process_rules().is_empty()
+            || !is_due(self.last_process_rules_check, PROCESS_RULES_CHECK_MS)
+        {
+            return;
+        }
+        self.last_process_rules_check = Some(Instant::now());
+
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_memory()
+                .with_cmd(UpdateKind::OnlyIfNotSet),
+        );
+
+        let rules = self.config.active_process_rules().to_vec();
+        for (index, rule) in rules.iter().enumerate() {
+            let cooldown = rule.cooldown_secs.unwrap_or(AUTO_EXECUTION_COOLDOWN_SECS);
+            let cooling_down = self
+                .process_rule_last_fired
+                .get(&index)
+                .is_some_and(|time| time.elapsed().as_secs() <= cooldown);
+            if cooling_down {
+                continue;
+            }
+
+            let offenders = self.find_offenders(rule);
+            if offenders.is_empty() {
+                continue;
+            }
+            for offender in &offenders {
+                self.add_log(
+                    format!(
+                        "Process rule {} matched {} (pid {}, RSS {}), {}",
+                        rule.describe(),
+                        offender.name,
+                        offender.pid,
+                        format_bytes(offender.rss),
+                        rule.action.describe()
+                    ),
+                    false,
+                );
+                self.run_process_action(&rule.action, offender);
+            }
+            self.process_rule_last_fired.insert(index, Instant::now());
+        }
+    }
+
+    /// Reaps the commands started by process rules that have finished, logging failed ones
+    pub fn poll_process_rule_commands(&mut self) {
+        let mut messages = Vec::new();
+        self.process_rule_runs
+            .retain_mut(|(command, child)| match child.try_wait() {
+                Ok(None) => true,
+                Ok(Some(status)) => {
+                    if !status.success() {
+                        messages.push(format!("{command} {}", describe_status(status)));
+                    }
+                    false
+                }
+                Err(e) => {
+                    messages.push(format!("Lost track of {command}: {e}"));
+                    false
+                }
+            });
+
+        for message in messages {
+            self.add_log(message, true);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::{fs, path::Path};
+
+/// Reads a kernel-provided text file and trims surrounding whitespace
+///
+/// # Returns
+/// * `None` if the file doesn't exist or can't be read (e.g. on non-Linux systems)
+pub fn read_trimmed(path: impl AsRef<Path>) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+}
+
+/// Reads a single unsigned integer from a kernel-provided file
+pub fn read_u64(path: impl AsRef<Path>) -> Option<u64> {
+    read_trimmed(path)?.parse().ok()
+}
+
+/// Parses "key value" lines such as those found in memory.stat or memory.events
+///
+/// # Returns
+/// * Iterator of (key, value) pairs, skipping lines that don't parse
+pub fn parse_key_values(contents: &str) -> impl Iterator<Item = (&str, u64)> {
+    contents.lines().filter_map(|line| {
+        let mut parts = line.split_whitespace();
+        let key = parts.next()?.trim_end_matches(':');
+        let value = parts.next()?.parse().ok()?;
+        Some((key, value))
+    })
+}
+
+/// Looks up a single key in "key value" formatted contents
+pub fn find_value(contents: &str, key: &str) -> Option<u64> {
+    parse_key_values(contents)
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+
+/// Returns the system page size in bytes
+pub fn page_size() -> u64 {
+    #[cfg(unix)]
+    {
+        // SAFETY: sysconf has no preconditions and only reads a system constant
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if size > 0 {
+            return size as u64;
+        }
+    }
+    4096
+}
+
+/// Returns true when running with root privileges, which writing sysctls requires
+pub fn is_root() -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: geteuid has no preconditions and cannot fail
+        unsafe { libc::geteuid() == 0 }
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
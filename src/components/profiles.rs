@@ -0,0 +1,151 @@
+use crate::components::{
+    constants::DEFAULT_PROFILE,
+    memory_management::{Action, ActionId},
+    structs::{AutoRule, Config, ProcessRule, Profile, RamMonitor},
+};
+
+impl Config {
+    /// Returns the index of the profile with the given name
+    pub fn profile_index(&self, name: &str) -> Option<usize> {
+        self.profiles
+            .iter()
+            .position(|profile| profile.name == name)
+    }
+
+    /// Returns the active profile, or None when the base settings are in use
+    pub fn profile(&self) -> Option<&Profile> {
+        let name = self.active_profile.as_deref()?;
+        self.profile_index(name).map(|index| &self.profiles[index])
+    }
+
+    /// Returns the name of the active profile
+    pub fn profile_name(&self) -> &str {
+        self.profile()
+            .map_or(DEFAULT_PROFILE, |profile| profile.name.as_str())
+    }
+
+    /// Returns the auto-execution threshold of the active profile
+    pub fn active_threshold(&self) -> f32 {
+        self.profile()
+            .and_then(|profile| profile.auto_threshold)
+            .unwrap_or(self.auto_threshold)
+    }
+
+    /// Returns the auto-execution action of the active profile
+    pub fn active_auto_action(&self) -> &ActionId {
+        self.profile()
+            .and_then(|profile| profile.auto_action.as_ref())
+            .unwrap_or(&self.auto_action)
+    }
+
+    /// Returns the rules of the active profile
+    pub fn active_rules(&self) -> &[AutoRule] {
+        self.profile()
+            .and_then(|profile| profile.rules.as_deref())
+            .unwrap_or(&self.rules)
+    }
+
+    /// Returns the process rules of the active profile
+    pub fn active_process_rules(&self) -> &[ProcessRule] {
+        self.profile()
+            .and_then(|profile| profile.process_rules.as_deref())
+            .unwrap_or(&self.process_rules)
+    }
+
+    /// Changes the threshold in effect: the active profile's if it sets one, otherwise the base one
+    pub fn set_active_threshold(&mut self, threshold: f32) {
+        let profile = self
+            .active_profile
+            .clone()
+            .and_then(|name| self.profile_index(&name));
+        match profile.map(|index| &mut self.profiles[index]) {
+            Some(profile) if profile.auto_threshold.is_some() => {
+                profile.auto_threshold = Some(threshold);
+            }
+            _ => self.auto_threshold = threshold,
+        }
+    }
+
+    /// Changes the action in effect: the active profile's if it sets one, otherwise the base one
+    pub fn set_active_auto_action(&mut self, action: ActionId) {
+        let profile = self
+            .active_profile
+            .clone()
+            .and_then(|name| self.profile_index(&name));
+        match profile.map(|index| &mut self.profiles[index]) {
+            Some(profile) if profile.auto_action.is_some() => {
+                profile.auto_action = Some(action);
+            }
+            _ => self.auto_action = action,
+        }
+    }
+}
+
+impl RamMonitor {
+    /// Switches to the next profile, going back to the default settings after the last one
+    pub fn cycle_profile(&mut self) {
+        if self.config.profiles.is_empty() {
+            self.add_log("No profiles configured".to_string(), true);
+            return;
+        }
+
+        let next = match self.config.profile() {
+            Some(profile) => self
+                .config
+                .profile_index(&profile.name)
+                .and_then(|index| self.config.profiles.get(index + 1)),
+            None => self.config.profiles.first(),
+        };
+        let name = next
+            .map_or(DEFAULT_PROFILE, |profile| profile.name.as_str())
+            .to_string();
+        self.switch_profile(&name);
+    }
+
+    /// Activates a profile by name and saves the choice to the config
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `name` - The profile to activate, or "default" for the base settings
+    ///
+    /// # Returns
+    /// * `true` if the profile exists and is now active
+    pub fn switch_profile(&mut self, name: &str) -> bool {
+        let active_profile = if name == DEFAULT_PROFILE {
+            None
+        } else if self.config.profile_index(name).is_some() {
+            Some(name.to_string())
+        } else {
+            self.add_log(format!("Unknown profile {name}"), true);
+            return false;
+        };
+
+        if active_profile == self.config.active_profile {
+            return true;
+        }
+        self.config.active_profile = active_profile;
+        self.apply_profile();
+        self.add_log(
+            format!(
+                "Switched to profile {name} (threshold {}%, action {})",
+                self.auto_threshold,
+                Action::from_id(&self.auto_action, &self.config).map_or_else(
+                    |_| self.auto_action.describe(),
+                    |action| action.display_name(&self.config).to_string()
+                )
+            ),
+            false,
+        );
+        self.handle_config_save("active_profile");
+        true
+    }
+
+    /// Updates the auto-execution state from the active profile
+    /// Rule cooldowns are reset, as they are tracked by the index of a rule in the active list
+    pub fn apply_profile(&mut self) {
+        self.auto_threshold = self.config.active_threshold();
+        self.auto_action = self.config.active_auto_action().clone();
+        self.rule_last_fired.clear();
+        self.process_rule_last_fired.clear();
+    }
+}
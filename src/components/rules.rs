@@ -0,0 +1,85 @@
+use std::time::Instant;
+
+use crate::components::{
+    constants::AUTO_EXECUTION_COOLDOWN_SECS,
+    memory_management::{Action, ActionId},
+    structs::{RamMonitor, RuleTrigger},
+};
+
+/// Snapshot of the measurements rules are evaluated against on each tick
+pub struct RuleContext {
+    pub percentage: f32,
+    pub new_oom_kills: u64,
+    pub node_percentages: Vec<(usize, f32)>,
+    pub fragmentation_index: Option<f32>,
+}
+
+impl RuleTrigger {
+    /// Returns whether the trigger condition holds for the current measurements
+    fn is_triggered(&self, context: &RuleContext) -> bool {
+        match self {
+            Self::OomKill => context.new_oom_kills > 0,
+            Self::NodeThreshold { node, percent } => context
+                .node_percentages
+                .iter()
+                .any(|(id, usage)| id == node && usage >= percent),
+            Self::Fragmentation { percent } => context
+                .fragmentation_index
+                .map(|index| index >= *percent)
+                .unwrap_or(false),
+        }
+    }
+
+    /// Returns a human-readable description of the trigger for logging
+    pub fn describe(&self) -> String {
+        match self {
+            Self::OomKill => String::from("OOM kill"),
+            Self::NodeThreshold { node, percent } => format!("NUMA node {node} above {percent}%"),
+            Self::Fragmentation { percent } => format!("fragmentation above {percent}%"),
+        }
+    }
+}
+
+impl RamMonitor {
+    /// Evaluates the active profile's auto-execution rules and runs the actions of those that trigger
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `context` - Measurements taken this tick
+    ///
+    /// # Behavior
+    /// * Each rule has its own cooldown, defaulting to AUTO_EXECUTION_COOLDOWN_SECS
+    pub fn check_rules(&mut self, context: &RuleContext) {
+        let triggered: Vec<(usize, String, ActionId)> = self
+            .config
+            .active_rules()
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| rule.trigger.is_triggered(context))
+            .filter(|(index, rule)| {
+                let cooldown = rule.cooldown_secs.unwrap_or(AUTO_EXECUTION_COOLDOWN_SECS);
+                self.rule_last_fired
+                    .get(index)
+                    .map(|time| time.elapsed().as_secs() > cooldown)
+                    .unwrap_or(true)
+            })
+            .map(|(index, rule)| (index, rule.trigger.describe(), rule.action.clone()))
+            .collect();
+
+        for (index, trigger, action) in triggered {
+            let Ok(command) = Action::from_id(&action, &self.config) else {
+                continue;
+            };
+            self.add_log(
+                format!(
+                    "Rule triggered: {trigger} at {:.1}%, running {}",
+                    context.percentage,
+                    command.display_name(&self.config)
+                ),
+                false,
+            );
+            self.execute_action(command);
+            self.rule_last_fired.insert(index, Instant::now());
+        }
+    }
+}
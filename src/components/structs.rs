@@ -1,11 +1,226 @@
 use serde::{Deserialize, Serialize};
-use std::{collections::VecDeque, time::Instant};
-use sysinfo::System;
+use serde_json::{Map, Value};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs::File,
+    path::PathBuf,
+    process::Child,
+    sync::mpsc::Receiver,
+    time::{Instant, SystemTime},
+};
+use sysinfo::{Pid, System};
+
+use crate::components::{
+    cgroup::{CgroupNode, CgroupStats},
+    eviction::EvictionResult,
+    fragmentation::ZoneFreeBlocks,
+    hugepages::HugePageStats,
+    killer::KillerVictim,
+    memory_management::ActionId,
+    notify::PressureNotifier,
+    notify_client::PressureLevel,
+    numa::NumaNode,
+    oom::KernelLog,
+    page_cache::{CacheGrouping, CachedEntry, ScanMessage},
+    paths::Paths,
+    prewarm::{LockedMapping, PrewarmResult},
+    slab::{SlabCache, SlabSort, SlabTotals},
+    suspend::SuspendedProcess,
+    swap::{SwapDevice, ZswapStats},
+    sysctl::SysctlSetting,
+};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Config {
+    #[serde(default)]
+    pub version: u32,
     pub auto_threshold: f32,
-    pub auto_action: String,
+    pub auto_action: ActionId,
+    #[serde(default)]
+    pub forecast_lead_secs: Option<u64>,
+    #[serde(default)]
+    pub cgroup_root: Option<String>,
+    #[serde(default)]
+    pub rules: Vec<AutoRule>,
+    #[serde(default)]
+    pub page_cache_dirs: Vec<String>,
+    #[serde(default)]
+    pub eviction_targets: Vec<EvictionTarget>,
+    #[serde(default)]
+    pub prewarm_paths: Vec<String>,
+    #[serde(default)]
+    pub prewarm_lock_bytes: Option<u64>,
+    #[serde(default)]
+    pub oom_killer: Option<OomKillerConfig>,
+    #[serde(default)]
+    pub suspend: Option<SuspendConfig>,
+    #[serde(default)]
+    pub notify_socket: Option<String>,
+    #[serde(default)]
+    pub process_rules: Vec<ProcessRule>,
+    #[serde(default)]
+    pub custom_actions: Vec<CustomAction>,
+    #[serde(default)]
+    pub macros: Vec<ActionMacro>,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    /// Fields this version doesn't know, kept so saving the config doesn't drop them
+    #[serde(flatten)]
+    pub unknown: Map<String, Value>,
+}
+
+/// A named policy that replaces the auto-execution settings it sets while it is active
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Profile {
+    pub name: String,
+    #[serde(default)]
+    pub auto_threshold: Option<f32>,
+    #[serde(default)]
+    pub auto_action: Option<ActionId>,
+    #[serde(default)]
+    pub rules: Option<Vec<AutoRule>>,
+    #[serde(default)]
+    pub process_rules: Option<Vec<ProcessRule>>,
+}
+
+/// A named sequence of RAMMap commands and custom actions
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ActionMacro {
+    pub name: String,
+    pub steps: Vec<MacroStep>,
+    #[serde(default)]
+    pub hotkey: Option<char>,
+    #[serde(default)]
+    pub continue_on_failure: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MacroStep {
+    pub action: ActionId,
+    #[serde(default)]
+    pub delay_secs: Option<u64>,
+}
+
+/// Progress of the macro being executed
+pub struct MacroRun {
+    pub index: usize,
+    pub step: usize,
+    pub step_started: Instant,
+    pub child: Option<Child>,
+    pub failures: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CustomAction {
+    pub name: String,
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    #[serde(default)]
+    pub privileges: Privileges,
+    #[serde(default)]
+    pub hotkey: Option<char>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Privileges {
+    #[default]
+    User,
+    Root,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProcessRule {
+    #[serde(rename = "match")]
+    pub matcher: ProcessMatcher,
+    #[serde(default)]
+    pub rss_bytes: Option<u64>,
+    #[serde(default)]
+    pub swap_bytes: Option<u64>,
+    pub action: ProcessAction,
+    #[serde(default)]
+    pub cooldown_secs: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ProcessMatcher {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub cmdline: Option<String>,
+    #[serde(default)]
+    pub cgroup: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProcessAction {
+    Signal { signal: String },
+    Command { command: String },
+    Trim,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SuspendConfig {
+    pub processes: Vec<String>,
+    pub percent: f32,
+    pub recovery_percent: f32,
+    #[serde(default)]
+    pub max_suspend_secs: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OomKillerConfig {
+    pub mem_available_percent: f32,
+    #[serde(default)]
+    pub swap_free_percent: Option<f32>,
+    #[serde(default)]
+    pub prefer: VictimPreference,
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    #[serde(default)]
+    pub grace_secs: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum VictimPreference {
+    #[default]
+    OomScore,
+    Rss,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EvictionTarget {
+    pub name: String,
+    pub paths: Vec<String>,
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AutoRule {
+    pub trigger: RuleTrigger,
+    pub action: ActionId,
+    #[serde(default)]
+    pub cooldown_secs: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleTrigger {
+    OomKill,
+    NodeThreshold { node: usize, percent: f32 },
+    Fragmentation { percent: f32 },
 }
 
 pub enum ActivityState {
@@ -13,22 +228,106 @@ pub enum ActivityState {
     Idle,
 }
 
+pub enum MemoryScope {
+    Host,
+    Cgroup,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum View {
+    Logs,
+    Cgroups,
+    Swap,
+    Numa,
+    HugePages,
+    Fragmentation,
+    Slab,
+    PageCache,
+    Sysctl,
+    Suspended,
+}
+
 pub struct LogEntry {
     pub message: String,
     pub timestamp: Instant,
     pub is_error: bool,
 }
 
+pub struct Sample {
+    pub timestamp: Instant,
+    pub percentage: f32,
+}
+
 pub struct RamMonitor {
     pub system: System,
     pub logs: VecDeque<LogEntry>,
+    pub history: VecDeque<Sample>,
     pub auto_threshold: f32,
-    pub auto_action: String,
+    pub auto_action: ActionId,
     pub last_auto_execution: Option<Instant>,
     pub selected_action: usize,
     pub last_key_press: Option<Instant>,
     pub last_action: Option<Instant>,
     pub last_activity: Instant,
     pub activity_state: ActivityState,
+    pub memory_scope: MemoryScope,
+    pub cgroup: Option<CgroupStats>,
+    pub last_cgroup_refresh: Option<Instant>,
+    pub cgroup_tree: Vec<CgroupNode>,
+    pub last_cgroup_tree_refresh: Option<Instant>,
+    pub view: View,
+    pub view_selection: usize,
+    pub oom_kills: u64,
+    pub last_oom_kill_count: Option<u64>,
+    pub cgroup_oom_kills: HashMap<PathBuf, u64>,
+    pub last_oom_check: Option<Instant>,
+    pub kernel_log: Option<KernelLog>,
+    pub rule_last_fired: HashMap<usize, Instant>,
+    pub process_rule_last_fired: HashMap<usize, Instant>,
+    pub last_process_rules_check: Option<Instant>,
+    pub custom_runs: Vec<(String, Child)>,
+    pub process_rule_runs: Vec<(String, Child)>,
+    pub macro_run: Option<MacroRun>,
+    pub swap_devices: Vec<SwapDevice>,
+    pub zswap: Option<ZswapStats>,
+    pub last_swap_refresh: Option<Instant>,
+    pub numa_nodes: Vec<NumaNode>,
+    pub last_numa_refresh: Option<Instant>,
+    pub hugepages: Option<HugePageStats>,
+    pub last_hugepages_refresh: Option<Instant>,
+    pub buddyinfo: Vec<ZoneFreeBlocks>,
+    pub pagetypeinfo: Vec<ZoneFreeBlocks>,
+    pub compaction_recommended: bool,
+    pub last_fragmentation_refresh: Option<Instant>,
+    pub slab_caches: Vec<SlabCache>,
+    pub slab_baseline: HashMap<String, u64>,
+    pub slab_totals: Option<SlabTotals>,
+    pub slab_total_baseline: Option<u64>,
+    pub slab_sort: SlabSort,
+    pub last_slab_refresh: Option<Instant>,
+    pub page_cache_scan: Option<Receiver<ScanMessage>>,
+    pub page_cache_progress: usize,
+    pub page_cache_files: Vec<CachedEntry>,
+    pub page_cache_dirs_grouped: Vec<CachedEntry>,
+    pub page_cache_grouping: CacheGrouping,
+    pub evictions: Vec<(String, Receiver<EvictionResult>)>,
+    pub prewarm: Option<Receiver<PrewarmResult>>,
+    pub prewarm_scheduled: Option<Instant>,
+    pub prewarm_locked: Vec<LockedMapping>,
+    pub sysctls: Vec<SysctlSetting>,
+    pub last_sysctl_refresh: Option<Instant>,
+    pub killer_victim: Option<KillerVictim>,
+    pub killer_critical: bool,
+    pub last_killer_exit: Option<Instant>,
+    pub last_killer_check: Option<Instant>,
+    pub suspended: Vec<SuspendedProcess>,
+    pub suspend_exempt: HashSet<Pid>,
+    pub last_suspend_check: Option<Instant>,
+    pub notifier: Option<PressureNotifier>,
+    pub pressure_level: PressureLevel,
+    pub paths: Paths,
+    pub config_modified: Option<SystemTime>,
+    pub last_config_check: Option<Instant>,
+    pub log_file: Option<File>,
     pub config: Config,
 }
@@ -0,0 +1,254 @@
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicU32, Ordering},
+    time::Instant,
+};
+
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal};
+
+use crate::components::{
+    constants::{DEFAULT_MAX_SUSPEND_SECS, MAX_TRACKED_STOPPED, SUSPEND_CHECK_MS},
+    structs::RamMonitor,
+    utils::is_due,
+};
+
+/// A process stopped with SIGSTOP, waiting for memory to recover
+pub struct SuspendedProcess {
+    pub pid: Pid,
+    pub name: String,
+    pub since: Instant,
+}
+
+/// Pids of the processes the observer has stopped, kept outside RamMonitor so that
+/// a panic hook or signal handler can still resume them. Free slots hold 0; atomics
+/// are used because a signal handler can't safely take a lock
+static STOPPED_PIDS: [AtomicU32; MAX_TRACKED_STOPPED] =
+    [const { AtomicU32::new(0) }; MAX_TRACKED_STOPPED];
+
+/// Records that a process was stopped or resumed
+fn track_stopped(pid: Pid, stopped: bool) {
+    let pid = pid.as_u32();
+    if stopped {
+        // Claims the first free slot; when all are taken the pid isn't tracked
+        STOPPED_PIDS.iter().any(|slot| {
+            slot.compare_exchange(0, pid, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+        });
+    } else {
+        for slot in &STOPPED_PIDS {
+            let _ = slot.compare_exchange(pid, 0, Ordering::SeqCst, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Sends SIGCONT to every process the observer still has stopped
+/// This is the fallback for exit paths that can't reach RamMonitor::resume_all,
+/// such as an error returned from main, a panic or a termination signal.
+/// It only uses atomics and kill, so it is safe to call from a signal handler
+pub fn resume_stopped() {
+    for slot in &STOPPED_PIDS {
+        let pid = slot.swap(0, Ordering::SeqCst);
+        #[cfg(unix)]
+        if pid != 0 {
+            // SAFETY: kill has no memory safety requirements
+            unsafe { libc::kill(pid as libc::pid_t, libc::SIGCONT) };
+        }
+        #[cfg(not(unix))]
+        let _ = pid;
+    }
+}
+
+/// Resumes the stopped processes, then lets the signal terminate the observer
+#[cfg(unix)]
+extern "C" fn resume_on_signal(signal: libc::c_int) {
+    resume_stopped();
+    // SAFETY: the handler was installed with SA_RESETHAND, so raising the signal again
+    // runs its default action
+    unsafe { libc::raise(signal) };
+}
+
+/// Installs handlers for SIGTERM, SIGHUP and SIGINT that resume the stopped processes
+/// before the observer is terminated, which neither Drop nor the panic hook cover
+#[cfg(unix)]
+pub fn install_signal_handlers() {
+    use std::{mem, ptr};
+
+    // SAFETY: sigaction is zero-initialisable, and the handler only calls
+    // async-signal-safe functions
+    unsafe {
+        let mut action: libc::sigaction = mem::zeroed();
+        action.sa_sigaction = resume_on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESETHAND;
+        libc::sigemptyset(&mut action.sa_mask);
+        for signal in [libc::SIGTERM, libc::SIGHUP, libc::SIGINT] {
+            libc::sigaction(signal, &action, ptr::null_mut());
+        }
+    }
+}
+
+impl RamMonitor {
+    /// Sends SIGSTOP to every configured process that isn't suspended or exempt yet
+    fn suspend_processes(&mut self, names: &[String], percentage: f32) {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+        let suspended: HashSet<Pid> = self.suspended.iter().map(|process| process.pid).collect();
+
+        let targets: Vec<(Pid, String)> = self
+            .system
+            .processes()
+            .iter()
+            .filter(|(pid, process)| {
+                process.thread_kind().is_none()
+                    && !suspended.contains(pid)
+                    && !self.suspend_exempt.contains(pid)
+            })
+            .map(|(pid, process)| (*pid, process.name().to_string_lossy().to_string()))
+            .filter(|(_, name)| names.contains(name))
+            .collect();
+
+        for (pid, name) in targets {
+            let sent = self
+                .system
+                .process(pid)
+                .and_then(|process| process.kill_with(Signal::Stop));
+            if sent == Some(true) {
+                self.add_log(
+                    format!("Suspended {name} (pid {pid}) at {percentage:.1}%"),
+                    false,
+                );
+                track_stopped(pid, true);
+                self.suspended.push(SuspendedProcess {
+                    pid,
+                    name,
+                    since: Instant::now(),
+                });
+            } else {
+                self.add_log(format!("Failed to suspend {name} (pid {pid})"), true);
+            }
+        }
+    }
+
+    /// Sends SIGCONT to a suspended process and stops tracking it
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `index` - Index of the process in the suspended list
+    /// * `reason` - Why the process is resumed, for the log
+    fn resume_process(&mut self, index: usize, reason: &str) {
+        if index >= self.suspended.len() {
+            return;
+        }
+        let process = self.suspended.remove(index);
+        track_stopped(process.pid, false);
+
+        self.system
+            .refresh_processes(ProcessesToUpdate::Some(&[process.pid]), true);
+        let sent = self
+            .system
+            .process(process.pid)
+            .and_then(|p| p.kill_with(Signal::Continue));
+        match sent {
+            Some(true) => self.add_log(
+                format!(
+                    "Resumed {} (pid {}) after {}s, {reason}",
+                    process.name,
+                    process.pid,
+                    process.since.elapsed().as_secs()
+                ),
+                false,
+            ),
+            Some(false) => self.add_log(
+                format!("Failed to resume {} (pid {})", process.name, process.pid),
+                true,
+            ),
+            None => self.add_log(
+                format!(
+                    "{} (pid {}) exited while suspended",
+                    process.name, process.pid
+                ),
+                false,
+            ),
+        }
+    }
+
+    /// Suspends configured processes under pressure and resumes them on recovery,
+    /// at most once per SUSPEND_CHECK_MS
+    ///
+    /// # Behavior
+    /// * Processes are suspended once usage reaches `percent`
+    /// * All are resumed once usage falls below `recovery_percent`
+    /// * A process suspended longer than `max_suspend_secs` is resumed and left
+    ///   running until usage recovers
+    pub fn check_suspend(&mut self, percentage: f32) {
+        let Some(suspend) = self.config.suspend.clone() else {
+            return;
+        };
+        if !is_due(self.last_suspend_check, SUSPEND_CHECK_MS) {
+            return;
+        }
+        self.last_suspend_check = Some(Instant::now());
+
+        if percentage < suspend.recovery_percent {
+            self.suspend_exempt.clear();
+            if !self.suspended.is_empty() {
+                self.resume_all(&format!("usage recovered to {percentage:.1}%"));
+            }
+            return;
+        }
+
+        let max_secs = suspend.max_suspend_secs.unwrap_or(DEFAULT_MAX_SUSPEND_SECS);
+        while let Some(index) = self
+            .suspended
+            .iter()
+            .position(|process| process.since.elapsed().as_secs() >= max_secs)
+        {
+            self.suspend_exempt.insert(self.suspended[index].pid);
+            self.resume_process(index, &format!("suspended longer than {max_secs}s"));
+        }
+
+        if percentage >= suspend.percent {
+            self.suspend_processes(&suspend.processes, percentage);
+        }
+    }
+
+    /// Resumes the process selected in the suspended processes view
+    /// It stays running until usage recovers
+    pub fn resume_selected_process(&mut self) {
+        let index = self.view_selection;
+        if let Some(process) = self.suspended.get(index) {
+            self.suspend_exempt.insert(process.pid);
+            self.resume_process(index, "resumed manually");
+            self.view_selection = self
+                .view_selection
+                .min(self.suspended.len().saturating_sub(1));
+        }
+    }
+
+    /// Resumes the suspended processes the reloaded `suspend` policy no longer covers,
+    /// or all of them when the policy was removed
+    pub fn apply_suspend_change(&mut self) {
+        let Some(suspend) = &self.config.suspend else {
+            self.resume_all("suspend policy removed");
+            return;
+        };
+
+        let names = suspend.processes.clone();
+        while let Some(index) = self
+            .suspended
+            .iter()
+            .position(|process| !names.contains(&process.name))
+        {
+            self.resume_process(index, "no longer matched by the suspend policy");
+        }
+    }
+
+    /// Resumes every suspended process, e.g. on recovery or before exiting
+    pub fn resume_all(&mut self, reason: &str) {
+        while !self.suspended.is_empty() {
+            self.resume_process(0, reason);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::{fs, path::Path, time::Instant};
+
+use crate::components::{
+    constants::SWAP_REFRESH_MS,
+    procfs::{find_value, page_size, read_trimmed, read_u64},
+    structs::RamMonitor,
+    utils::is_due,
+};
+
+/// A swap device or file from /proc/swaps
+pub struct SwapDevice {
+    pub name: String,
+    pub kind: String,
+    pub size: u64,
+    pub used: u64,
+    pub priority: i32,
+    pub zram: Option<ZramStats>,
+}
+
+/// Compression statistics of a zram block device, from /sys/block/zramN/mm_stat
+pub struct ZramStats {
+    pub algorithm: Option<String>,
+    pub orig_data_size: u64,
+    pub compr_data_size: u64,
+    pub mem_used_total: u64,
+}
+
+/// State of the zswap compressed swap cache
+pub struct ZswapStats {
+    pub enabled: bool,
+    pub compressor: Option<String>,
+    pub max_pool_percent: Option<u64>,
+    pub pool_size: Option<u64>,
+    pub stored_size: Option<u64>,
+}
+
+impl ZramStats {
+    /// Ratio of uncompressed to compressed data, if anything is stored
+    pub fn compression_ratio(&self) -> Option<f32> {
+        (self.compr_data_size > 0).then(|| self.orig_data_size as f32 / self.compr_data_size as f32)
+    }
+}
+
+impl ZswapStats {
+    /// Ratio of uncompressed to compressed data, if anything is stored
+    pub fn compression_ratio(&self) -> Option<f32> {
+        match (self.stored_size, self.pool_size) {
+            (Some(stored), Some(pool)) if pool > 0 => Some(stored as f32 / pool as f32),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the active compression algorithm, shown in brackets in comp_algorithm
+fn read_zram_algorithm(block: &Path) -> Option<String> {
+    let algorithms = read_trimmed(block.join("comp_algorithm"))?;
+    algorithms
+        .split_whitespace()
+        .find_map(|name| name.strip_prefix('[')?.strip_suffix(']'))
+        .map(str::to_string)
+}
+
+/// Reads mm_stat for a zram device such as /dev/zram0
+fn read_zram(device: &str) -> Option<ZramStats> {
+    let name = Path::new(device).file_name()?.to_str()?;
+    if !name.starts_with("zram") {
+        return None;
+    }
+
+    let block = Path::new("/sys/block").join(name);
+    let mm_stat = read_trimmed(block.join("mm_stat"))?;
+    let fields: Vec<u64> = mm_stat
+        .split_whitespace()
+        .filter_map(|field| field.parse().ok())
+        .collect();
+
+    Some(ZramStats {
+        algorithm: read_zram_algorithm(&block),
+        orig_data_size: *fields.first()?,
+        compr_data_size: *fields.get(1)?,
+        mem_used_total: *fields.get(2)?,
+    })
+}
+
+/// Parses /proc/swaps, whose sizes are reported in KiB
+pub fn read_swap_devices() -> Vec<SwapDevice> {
+    let Ok(contents) = fs::read_to_string("/proc/swaps") else {
+        return Vec::new();
+    };
+
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [name, kind, size, used, priority] = fields.as_slice() else {
+                return None;
+            };
+            Some(SwapDevice {
+                name: name.to_string(),
+                kind: kind.to_string(),
+                size: size.parse::<u64>().ok()? * 1024,
+                used: used.parse::<u64>().ok()? * 1024,
+                priority: priority.parse().ok()?,
+                zram: read_zram(name),
+            })
+        })
+        .collect()
+}
+
+/// Reads zswap parameters and pool statistics
+///
+/// # Returns
+/// * `None` if the kernel has no zswap support
+/// * Pool sizes come from the Zswap/Zswapped lines in /proc/meminfo, falling
+///   back to debugfs, and are `None` when neither is readable
+pub fn read_zswap() -> Option<ZswapStats> {
+    let parameters = Path::new("/sys/module/zswap/parameters");
+    let enabled = read_trimmed(parameters.join("enabled"))?;
+    let meminfo = fs::read_to_string("/proc/meminfo").unwrap_or_default();
+    let debugfs = Path::new("/sys/kernel/debug/zswap");
+    let page_size = page_size();
+
+    Some(ZswapStats {
+        enabled: enabled == "Y",
+        compressor: read_trimmed(parameters.join("compressor")),
+        max_pool_percent: read_u64(parameters.join("max_pool_percent")),
+        pool_size: find_value(&meminfo, "Zswap")
+            .map(|kb| kb * 1024)
+            .or_else(|| read_u64(debugfs.join("pool_total_size"))),
+        stored_size: find_value(&meminfo, "Zswapped")
+            .map(|kb| kb * 1024)
+            .or_else(|| read_u64(debugfs.join("stored_pages")).map(|pages| pages * page_size)),
+    })
+}
+
+impl RamMonitor {
+    /// Re-reads swap devices and zswap statistics at most once per SWAP_REFRESH_MS
+    pub fn refresh_swap_devices(&mut self) {
+        if !is_due(self.last_swap_refresh, SWAP_REFRESH_MS) {
+            return;
+        }
+
+        self.swap_devices = read_swap_devices();
+        self.zswap = read_zswap();
+        self.last_swap_refresh = Some(Instant::now());
+    }
+}
@@ -0,0 +1,207 @@
+use std::{fs, path::PathBuf, time::Instant};
+
+use crate::components::{
+    constants::{SYSCTL_REFRESH_MS, SYSCTL_VM_DIR},
+    procfs::{is_root, read_u64},
+    structs::RamMonitor,
+    utils::is_due,
+};
+
+/// A vm sysctl the settings panel can edit, with the step used by +/- and its bounds
+/// The lower bound keeps values the kernel accepts but that risk reclaim or allocation failures
+/// out of reach, such as min_free_kbytes = 0
+pub struct Tunable {
+    pub name: &'static str,
+    pub step: u64,
+    pub min: u64,
+    pub max: u64,
+}
+
+/// The vm sysctls shown in the settings panel
+pub const VM_TUNABLES: [Tunable; 5] = [
+    Tunable {
+        name: "swappiness",
+        step: 10,
+        min: 0,
+        max: 200,
+    },
+    Tunable {
+        name: "vfs_cache_pressure",
+        step: 25,
+        min: 1,
+        max: 1000,
+    },
+    Tunable {
+        name: "dirty_ratio",
+        step: 5,
+        min: 1,
+        max: 100,
+    },
+    Tunable {
+        name: "dirty_background_ratio",
+        step: 5,
+        min: 1,
+        max: 100,
+    },
+    Tunable {
+        name: "min_free_kbytes",
+        step: 8192,
+        min: 8192,
+        max: 1_048_576,
+    },
+];
+
+/// Current, pending and startup value of one tunable
+pub struct SysctlSetting {
+    pub tunable: &'static Tunable,
+    pub current: Option<u64>,
+    pub original: Option<u64>,
+    pub pending: Option<u64>,
+}
+
+impl SysctlSetting {
+    /// Returns the file under /proc/sys/vm holding the value
+    pub fn path(&self) -> PathBuf {
+        PathBuf::from(SYSCTL_VM_DIR).join(self.tunable.name)
+    }
+
+    /// Returns the dotted sysctl name, e.g. "vm.swappiness"
+    pub fn display_name(&self) -> String {
+        format!("vm.{}", self.tunable.name)
+    }
+
+    /// Returns true when an edited value differs from the current one
+    pub fn is_modified(&self) -> bool {
+        self.pending.is_some() && self.pending != self.current
+    }
+}
+
+/// Reads every tunable, capturing the values as the startup values to revert to
+pub fn read_sysctls() -> Vec<SysctlSetting> {
+    VM_TUNABLES
+        .iter()
+        .map(|tunable| {
+            let value = read_u64(PathBuf::from(SYSCTL_VM_DIR).join(tunable.name));
+            SysctlSetting {
+                tunable,
+                current: value,
+                original: value,
+                pending: None,
+            }
+        })
+        .collect()
+}
+
+impl RamMonitor {
+    /// Re-reads current sysctl values at most once per SYSCTL_REFRESH_MS
+    pub fn refresh_sysctls(&mut self) {
+        if !is_due(self.last_sysctl_refresh, SYSCTL_REFRESH_MS) {
+            return;
+        }
+
+        for setting in &mut self.sysctls {
+            setting.current = read_u64(setting.path());
+        }
+        self.last_sysctl_refresh = Some(Instant::now());
+    }
+
+    /// Raises or lowers the pending value of the selected sysctl by its step
+    pub fn adjust_selected_sysctl(&mut self, increase: bool) {
+        let Some(setting) = self.sysctls.get_mut(self.view_selection) else {
+            return;
+        };
+        let Some(value) = setting.pending.or(setting.current) else {
+            return;
+        };
+
+        // A value already outside the bounds is never moved further out by a step
+        let Tunable { step, min, max, .. } = *setting.tunable;
+        setting.pending = Some(if increase {
+            (value + step).min(max.max(value))
+        } else {
+            value.saturating_sub(step).max(min.min(value))
+        });
+    }
+
+    /// Writes a value to a sysctl after checking privileges, logging the change
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `index` - Index of the setting in the sysctl list
+    /// * `value` - The value to write
+    fn write_sysctl(&mut self, index: usize, value: u64) {
+        let Some(setting) = self.sysctls.get(index) else {
+            return;
+        };
+        let name = setting.display_name();
+        let path = setting.path();
+        let previous = setting.current;
+
+        if !is_root() {
+            self.add_log(format!("Changing {name} requires root"), true);
+            return;
+        }
+
+        match fs::write(&path, value.to_string()) {
+            Ok(()) => {
+                let current = read_u64(&path);
+                if let Some(setting) = self.sysctls.get_mut(index) {
+                    setting.current = current;
+                    setting.pending = None;
+                }
+                let previous = previous.map_or_else(|| "?".to_string(), |v| v.to_string());
+                self.add_log(format!("Changed {name}: {previous} -> {value}"), false);
+            }
+            Err(e) => {
+                self.add_log(format!("Failed to write {}: {e}", path.display()), true);
+            }
+        }
+    }
+
+    /// Applies the pending value of the selected sysctl
+    pub fn apply_selected_sysctl(&mut self) {
+        let index = self.view_selection;
+        match self.sysctls.get(index) {
+            Some(setting) if setting.is_modified() => {
+                if let Some(value) = setting.pending {
+                    self.write_sysctl(index, value);
+                }
+            }
+            Some(setting) => {
+                let name = setting.display_name();
+                self.add_log(format!("No pending change for {name}"), false);
+            }
+            None => {}
+        }
+    }
+
+    /// Reverts sysctls to the values captured at startup
+    ///
+    /// # Arguments
+    /// * `self` - Mutable reference to RamMonitor instance
+    /// * `all` - Revert every changed sysctl instead of only the selected one
+    pub fn revert_sysctls(&mut self, all: bool) {
+        let indices: Vec<usize> = if all {
+            (0..self.sysctls.len()).collect()
+        } else {
+            vec![self.view_selection]
+        };
+
+        let mut reverted = false;
+        for index in indices {
+            let Some(setting) = self.sysctls.get_mut(index) else {
+                continue;
+            };
+            setting.pending = None;
+            if let Some(original) = setting.original
+                && setting.current != Some(original)
+            {
+                self.write_sysctl(index, original);
+                reverted = true;
+            }
+        }
+        if !reverted {
+            self.add_log("Sysctls already at startup values".to_string(), false);
+        }
+    }
+}
@@ -3,52 +3,114 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph},
+    widgets::{
+        Block, Borders, Cell, Gauge, List, ListItem, ListState, Paragraph, Row, Table, TableState,
+    },
 };
+use std::time::Duration;
 
 use crate::components::{
-    memory_management::Commands, structs::RamMonitor, utils::format_timestamp,
+    constants::{DEFAULT_MAX_SUSPEND_SECS, FRAGMENTATION_ORDER},
+    fragmentation::{ZoneFreeBlocks, overall_fragmentation_index},
+    hugepages::ThpMode,
+    memory_management::Action,
+    page_cache::CacheGrouping,
+    structs::{Config, RamMonitor, View},
+    utils::{
+        bytes_to_gb, format_bytes, format_bytes_delta, format_eta, format_timestamp,
+        get_usage_color,
+    },
 };
 
-pub fn create_layout(frame: &Frame<'_>) -> Vec<Rect> {
+impl View {
+    /// All views in the order Tab cycles through them
+    pub const ALL: [View; 10] = [
+        View::Logs,
+        View::Cgroups,
+        View::Swap,
+        View::Numa,
+        View::HugePages,
+        View::Fragmentation,
+        View::Slab,
+        View::PageCache,
+        View::Sysctl,
+        View::Suspended,
+    ];
+
+    /// Returns the panel title for the view
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Logs => "Logs",
+            Self::Cgroups => "Cgroups",
+            Self::Swap => "Swap Devices",
+            Self::Numa => "NUMA Nodes",
+            Self::HugePages => "Huge Pages",
+            Self::Fragmentation => "Fragmentation",
+            Self::Slab => "Slab Caches",
+            Self::PageCache => "Page Cache",
+            Self::Sysctl => "VM Settings",
+            Self::Suspended => "Suspended Processes",
+        }
+    }
+}
+
+/// Builds the bordered block shared by all bottom panel views, with key hints at the bottom
+fn view_block<'a>(view: View, hints: &'a str) -> Block<'a> {
+    Block::default()
+        .title(Span::styled(view.title(), Style::default().fg(Color::Cyan)))
+        .title_alignment(Alignment::Center)
+        .title_bottom(
+            Line::from(Span::styled(hints, Style::default().fg(Color::DarkGray))).centered(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+}
+
+pub fn create_layout(frame: &Frame<'_>, action_count: usize) -> Vec<Rect> {
     Layout::default()
         .direction(Direction::Vertical)
         .horizontal_margin(2)
         .constraints([
-            Constraint::Length(1), // Top margin
-            Constraint::Length(4), // RAM gauge
-            Constraint::Length(4), // Page File gauge
-            Constraint::Length(7), // Memory management
-            Constraint::Length(4), // Auto execution
-            Constraint::Min(2),    // Logs
-            Constraint::Length(1), // Bottom margin
+            Constraint::Length(1),                       // Top margin
+            Constraint::Length(4),                       // RAM gauge
+            Constraint::Length(4),                       // Page File gauge
+            Constraint::Length(action_count as u16 + 2), // Memory management
+            Constraint::Length(7),                       // Auto execution
+            Constraint::Min(2),                          // Logs
+            Constraint::Length(1),                       // Bottom margin
         ])
         .split(frame.area())
         .to_vec()
 }
 
+/// Builds a titled usage gauge filled to `percentage` and colored by usage level
+fn usage_gauge<'a>(title: &'a str, label: String, percentage: f32) -> Gauge<'a> {
+    Gauge::default()
+        .block(
+            Block::default()
+                .title(Span::styled(title, Style::default().fg(Color::Cyan)))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .gauge_style(Style::default().fg(get_usage_color(percentage)))
+        .ratio((percentage.clamp(0.0, 100.0) / 100.0) as f64)
+        .label(Span::styled(label, Style::default().fg(Color::White)))
+}
+
 pub fn render_ram_gauge(
     f: &mut Frame<'_>,
     area: Rect,
+    title: &str,
     used: f32,
     total: f32,
     percentage: f32,
-    color: Color,
 ) {
-    let gauge = Gauge::default()
-        .block(
-            Block::default()
-                .title(Span::styled("RAM Usage", Style::default().fg(Color::Cyan)))
-                .title_alignment(Alignment::Center)
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        )
-        .gauge_style(Style::default().fg(color))
-        .ratio((percentage / 100.0) as f64)
-        .label(Span::styled(
-            format!("{used:.1}GB / {total:.1}GB ({percentage:.1}%)"),
-            Style::default().fg(Color::White),
-        ));
+    let gauge = usage_gauge(
+        title,
+        format!("{used:.1}GB / {total:.1}GB ({percentage:.1}%)"),
+        percentage,
+    );
     f.render_widget(gauge, area);
 }
 
@@ -58,49 +120,80 @@ pub fn render_page_file_gauge(
     used: f32,
     total: f32,
     percentage: f32,
-    color: Color,
 ) {
-    let gauge = Gauge::default()
-        .block(
-            Block::default()
-                .title(Span::styled(
-                    "Page File Usage",
-                    Style::default().fg(Color::Cyan),
-                ))
-                .title_alignment(Alignment::Center)
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        )
-        .gauge_style(Style::default().fg(color))
-        .ratio((percentage / 100.0) as f64)
-        .label(Span::styled(
-            format!("{used:.1}GB / {total:.1}GB ({percentage:.1}%)"),
-            Style::default().fg(Color::White),
-        ));
+    let gauge = usage_gauge(
+        "Page File Usage",
+        format!("{used:.1}GB / {total:.1}GB ({percentage:.1}%)"),
+        percentage,
+    );
     f.render_widget(gauge, area);
 }
 
-pub fn render_memory_management(f: &mut Frame<'_>, area: Rect, selected_action: usize) {
-    let items: Vec<ListItem<'_>> = [
-        Commands::EmptyWorkingSets,
-        Commands::EmptySystemWorkingSets,
-        Commands::EmptyModifiedPageLists,
-        Commands::EmptyStandbyList,
-        Commands::EmptyPriorityZeroStandbyList,
-    ]
-    .iter()
-    .enumerate()
-    .map(|(i, action)| {
-        let prefix = if i == selected_action { ">> " } else { "   " };
-        let content = format!("{}{}", prefix, action.display_name());
-        let style = if i == selected_action {
-            Style::default().fg(Color::Yellow)
-        } else {
-            Style::default()
-        };
-        ListItem::new(content).style(style)
-    })
-    .collect();
+pub fn render_numa_nodes(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let block = view_block(View::Numa, "Tab: next view");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    if monitor.numa_nodes.is_empty() {
+        f.render_widget(
+            Paragraph::new("No NUMA nodes found in /sys/devices/system/node"),
+            inner,
+        );
+        return;
+    }
+
+    let titles: Vec<String> = monitor
+        .numa_nodes
+        .iter()
+        .map(|node| format!("Node {}", node.id))
+        .collect();
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(monitor.numa_nodes.iter().map(|_| Constraint::Length(3)))
+        .split(inner);
+
+    for ((node, title), row) in monitor.numa_nodes.iter().zip(&titles).zip(rows.iter()) {
+        let percentage = node.percentage();
+        let gauge = usage_gauge(
+            title,
+            format!(
+                "{:.1}GB / {:.1}GB ({percentage:.1}%) | free {:.1}GB, file {:.1}GB, anon {:.1}GB",
+                bytes_to_gb(node.used()),
+                bytes_to_gb(node.total),
+                bytes_to_gb(node.free),
+                bytes_to_gb(node.file),
+                bytes_to_gb(node.anon)
+            ),
+            percentage,
+        );
+        f.render_widget(gauge, *row);
+    }
+}
+
+pub fn render_memory_management(
+    f: &mut Frame<'_>,
+    area: Rect,
+    selected_action: usize,
+    config: &Config,
+) {
+    let items: Vec<ListItem<'_>> = Action::all(config)
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let prefix = if i == selected_action { ">> " } else { "   " };
+            let hotkey = action
+                .configured_hotkey(config)
+                .map(|key| format!(" [{key}]"))
+                .unwrap_or_default();
+            let content = format!("{}{}{hotkey}", prefix, action.display_name(config));
+            let style = if i == selected_action {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+            ListItem::new(content).style(style)
+        })
+        .collect();
 
     let list = List::new(items).block(
         Block::default()
@@ -115,18 +208,65 @@ pub fn render_memory_management(f: &mut Frame<'_>, area: Rect, selected_action:
     f.render_widget(list, area);
 }
 
-pub fn render_auto_execution(f: &mut Frame<'_>, area: Rect, threshold: f32, action: &str) {
+pub fn render_auto_execution(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let threshold = monitor.auto_threshold;
+    let profile_line = Line::from(vec![
+        Span::raw(format!("Profile: {} ", monitor.config.profile_name())),
+        Span::styled("(Shift+P to change)", Style::default().fg(Color::DarkGray)),
+    ]);
     let threshold_line = Line::from(vec![
         Span::raw(format!("Threshold: {threshold}% ")),
         Span::styled("(Shift+T to change)", Style::default().fg(Color::DarkGray)),
     ]);
 
     let action_line = Line::from(vec![
-        Span::raw(format!("Action: {action} ")),
+        Span::raw(format!(
+            "Action: {} ",
+            Action::from_id(&monitor.auto_action, &monitor.config).map_or_else(
+                |_| monitor.auto_action.describe(),
+                |action| { action.display_name(&monitor.config).to_string() }
+            )
+        )),
         Span::styled("(Shift+A to change)", Style::default().fg(Color::DarkGray)),
     ]);
 
-    let text = Text::from(vec![threshold_line, action_line]);
+    let forecast = match (
+        monitor.forecast_time_to(threshold),
+        monitor.forecast_time_to(100.0),
+    ) {
+        (Some(to_threshold), Some(to_full)) => format!(
+            "Forecast: {} to threshold, {} to 100%",
+            format_eta(to_threshold),
+            format_eta(to_full)
+        ),
+        (None, Some(to_full)) => format!("Forecast: {} to 100%", format_eta(to_full)),
+        _ => String::from("Forecast: stable"),
+    };
+    let forecast_style = match (
+        monitor.config.forecast_lead_secs,
+        monitor.forecast_time_to(threshold),
+    ) {
+        (Some(lead), Some(eta)) if eta.as_secs() <= lead => Style::default().fg(Color::Yellow),
+        _ => Style::default().fg(Color::DarkGray),
+    };
+    let forecast_line = Line::from(Span::styled(forecast, forecast_style));
+
+    let config_line = Line::from(Span::styled(
+        format!(
+            "Config: {} ({})",
+            monitor.paths.config.display(),
+            monitor.paths.origin.display_name()
+        ),
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let text = Text::from(vec![
+        profile_line,
+        threshold_line,
+        action_line,
+        forecast_line,
+        config_line,
+    ]);
 
     let paragraph = Paragraph::new(text).block(
         Block::default()
@@ -157,12 +297,500 @@ pub fn render_logs(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
         })
         .collect();
 
-    let list = List::new(logs).block(
-        Block::default()
-            .title(Span::styled("Logs", Style::default().fg(Color::Cyan)))
-            .title_alignment(Alignment::Center)
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray)),
-    );
+    let list = List::new(logs).block(view_block(View::Logs, "Tab: next view"));
     f.render_widget(list, area);
 }
+
+pub fn render_cgroup_tree(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let root = monitor.cgroup_root();
+    let hints = "Tab: next view | PgUp/PgDn: select | r: reclaim";
+    if monitor.cgroup_tree.is_empty() {
+        let paragraph = Paragraph::new(format!(
+            "No cgroup v2 hierarchy found at {}",
+            root.display()
+        ))
+        .block(view_block(View::Cgroups, hints));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let rows: Vec<ListItem<'_>> = monitor
+        .cgroup_tree
+        .iter()
+        .map(|node| {
+            let stats = &node.stats;
+            let name = stats
+                .path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| stats.display_path(&root));
+            let max = stats
+                .max
+                .map(|max| format!("{:.2}GB", bytes_to_gb(max)))
+                .unwrap_or_else(|| String::from("max"));
+            let swap = stats
+                .swap_current
+                .map(|swap| format!("{:.2}GB", bytes_to_gb(swap)))
+                .unwrap_or_else(|| String::from("-"));
+            let pressure = stats
+                .pressure_avg10
+                .map(|avg10| format!("{avg10:.2}%"))
+                .unwrap_or_else(|| String::from("-"));
+
+            ListItem::new(format!(
+                "{:<40} {:>8.2}GB / {:>8}  swap {:>8}  psi {:>7}",
+                format!("{}{}", "  ".repeat(node.depth), name),
+                bytes_to_gb(stats.current),
+                max,
+                swap,
+                pressure
+            ))
+        })
+        .collect();
+
+    let list = List::new(rows)
+        .block(view_block(View::Cgroups, hints))
+        .highlight_style(Style::default().fg(Color::Yellow))
+        .highlight_symbol(">> ");
+    let mut state = ListState::default().with_selected(Some(monitor.view_selection));
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+pub fn render_swap_devices(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let mut rows: Vec<ListItem<'_>> = vec![
+        ListItem::new(format!(
+            "{:<24} {:<10} {:>10} {:>10} {:>5}  {}",
+            "Device", "Type", "Size", "Used", "Prio", "Compression"
+        ))
+        .style(Style::default().fg(Color::DarkGray)),
+    ];
+
+    rows.extend(monitor.swap_devices.iter().map(|device| {
+        let compression = device
+            .zram
+            .as_ref()
+            .map(|zram| {
+                format!(
+                    "zram {}: {:.2}GB -> {:.2}GB ({}), {:.2}GB RAM used",
+                    zram.algorithm.as_deref().unwrap_or("?"),
+                    bytes_to_gb(zram.orig_data_size),
+                    bytes_to_gb(zram.compr_data_size),
+                    zram.compression_ratio()
+                        .map(|ratio| format!("{ratio:.2}x"))
+                        .unwrap_or_else(|| String::from("-")),
+                    bytes_to_gb(zram.mem_used_total)
+                )
+            })
+            .unwrap_or_default();
+
+        ListItem::new(format!(
+            "{:<24} {:<10} {:>8.2}GB {:>8.2}GB {:>5}  {}",
+            device.name,
+            device.kind,
+            bytes_to_gb(device.size),
+            bytes_to_gb(device.used),
+            device.priority,
+            compression
+        ))
+    }));
+
+    if monitor.swap_devices.is_empty() {
+        rows.push(ListItem::new("No swap devices found in /proc/swaps"));
+    }
+
+    if let Some(zswap) = &monitor.zswap {
+        let state = if zswap.enabled { "enabled" } else { "disabled" };
+        let pool = match (zswap.pool_size, zswap.stored_size) {
+            (Some(pool), Some(stored)) => format!(
+                ", pool {:.2}GB holding {:.2}GB ({})",
+                bytes_to_gb(pool),
+                bytes_to_gb(stored),
+                zswap
+                    .compression_ratio()
+                    .map(|ratio| format!("{ratio:.2}x"))
+                    .unwrap_or_else(|| String::from("-"))
+            ),
+            _ => String::new(),
+        };
+        rows.push(ListItem::new(""));
+        rows.push(ListItem::new(format!(
+            "zswap {state} ({}, max pool {}%){pool}",
+            zswap.compressor.as_deref().unwrap_or("?"),
+            zswap
+                .max_pool_percent
+                .map(|percent| percent.to_string())
+                .unwrap_or_else(|| String::from("?"))
+        )));
+    }
+
+    let list = List::new(rows).block(view_block(View::Swap, "Tab: next view"));
+    f.render_widget(list, area);
+}
+
+pub fn render_hugepages(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let hints = "Tab: next view | 6: compact | 7: THP enabled | 8: THP defrag";
+    let Some(stats) = &monitor.hugepages else {
+        let paragraph = Paragraph::new("No huge page information found")
+            .block(view_block(View::HugePages, hints));
+        f.render_widget(paragraph, area);
+        return;
+    };
+
+    let pool_gb = |pages: u64| bytes_to_gb(pages * stats.page_size);
+    let thp_line = |name: &str, mode: &Option<ThpMode>| {
+        let options = mode
+            .as_ref()
+            .map(|mode| {
+                mode.options
+                    .iter()
+                    .map(|option| {
+                        if *option == mode.current {
+                            Span::styled(format!("[{option}] "), Style::default().fg(Color::Yellow))
+                        } else {
+                            Span::styled(format!("{option} "), Style::default().fg(Color::DarkGray))
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_else(|| vec![Span::raw("unavailable")]);
+        Line::from([vec![Span::raw(format!("THP {name}: "))], options].concat())
+    };
+
+    let text = Text::from(vec![
+        Line::from(format!(
+            "HugePages: {} total ({:.2}GB), {} free, {} reserved, {} surplus ({}kB pages)",
+            stats.total,
+            pool_gb(stats.total),
+            stats.free,
+            stats.reserved,
+            stats.surplus,
+            stats.page_size / 1024
+        )),
+        Line::from(format!(
+            "Transparent huge pages: {:.2}GB anonymous, {:.2}GB shmem",
+            bytes_to_gb(stats.anon_huge),
+            bytes_to_gb(stats.shmem_huge)
+        )),
+        thp_line("enabled", &stats.thp_enabled),
+        thp_line("defrag", &stats.thp_defrag),
+    ]);
+
+    let paragraph = Paragraph::new(text).block(view_block(View::HugePages, hints));
+    f.render_widget(paragraph, area);
+}
+
+/// Colors a free block count from red (none left) to green (plenty)
+fn free_blocks_color(count: u64) -> Color {
+    match count {
+        0 => Color::Red,
+        1..=15 => Color::Yellow,
+        _ => Color::Green,
+    }
+}
+
+pub fn render_fragmentation(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let hints = "Tab: next view | 6: compact";
+    if monitor.buddyinfo.is_empty() {
+        let paragraph = Paragraph::new("No fragmentation information found in /proc/buddyinfo")
+            .block(view_block(View::Fragmentation, hints));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let orders = monitor
+        .buddyinfo
+        .iter()
+        .map(|zone| zone.counts.len())
+        .max()
+        .unwrap_or(0);
+
+    let zone_row = |zone: &ZoneFreeBlocks| {
+        let label = match &zone.migrate_type {
+            Some(migrate_type) => format!("  {migrate_type}"),
+            None => format!("{} {}", zone.node, zone.zone),
+        };
+        let index = zone
+            .fragmentation_index(FRAGMENTATION_ORDER)
+            .map(|index| format!("{index:.1}%"))
+            .unwrap_or_else(|| String::from("-"));
+
+        let mut cells = vec![Cell::from(label)];
+        cells.extend(zone.counts.iter().map(|count| {
+            Cell::from(count.to_string()).style(Style::default().fg(free_blocks_color(*count)))
+        }));
+        cells.resize(orders + 1, Cell::from(""));
+        cells.push(Cell::from(index));
+        Row::new(cells)
+    };
+
+    let mut rows = Vec::new();
+    for zone in &monitor.buddyinfo {
+        rows.push(zone_row(zone));
+        rows.extend(
+            monitor
+                .pagetypeinfo
+                .iter()
+                .filter(|t| t.node == zone.node && t.zone == zone.zone)
+                .map(|t| zone_row(t).style(Style::default().fg(Color::DarkGray))),
+        );
+    }
+
+    let mut header = vec![Cell::from("Node Zone")];
+    header.extend((0..orders).map(|order| Cell::from(order.to_string())));
+    header.push(Cell::from(format!("Frag@{FRAGMENTATION_ORDER}")));
+
+    let mut widths = vec![Constraint::Length(16)];
+    widths.extend((0..orders).map(|_| Constraint::Length(7)));
+    widths.push(Constraint::Length(10));
+
+    let summary = match (
+        overall_fragmentation_index(&monitor.buddyinfo),
+        monitor.compaction_recommended,
+    ) {
+        (Some(index), true) => Line::from(Span::styled(
+            format!(
+                "Fragmentation index {index:.1}%: no order-{FRAGMENTATION_ORDER} blocks left, compaction recommended"
+            ),
+            Style::default().fg(Color::Yellow),
+        )),
+        (Some(index), false) => Line::from(format!("Fragmentation index {index:.1}%")),
+        (None, _) => Line::from("No free memory reported"),
+    };
+
+    let block = view_block(View::Fragmentation, hints);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(inner);
+    f.render_widget(Paragraph::new(summary), chunks[0]);
+
+    let table = Table::new(rows, widths)
+        .header(Row::new(header).style(Style::default().fg(Color::DarkGray)));
+    f.render_widget(table, chunks[1]);
+}
+
+pub fn render_slab_caches(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let hints = "Tab: next view | PgUp/PgDn: scroll | s: sort";
+    let block = view_block(View::Slab, hints);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let summary = match (&monitor.slab_totals, monitor.slab_total_baseline) {
+        (Some(totals), Some(baseline)) => format!(
+            "Slab {} (reclaimable {}, unreclaimable {}), {} since start, sorted by {}",
+            format_bytes(totals.total),
+            format_bytes(totals.reclaimable),
+            format_bytes(totals.unreclaimable),
+            format_bytes_delta(totals.total as i64 - baseline as i64),
+            monitor.slab_sort.display_name()
+        ),
+        _ => String::from("No slab information found in /proc/meminfo"),
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(inner);
+    f.render_widget(Paragraph::new(summary), chunks[0]);
+
+    if monitor.slab_caches.is_empty() {
+        f.render_widget(
+            Paragraph::new("Per-cache details require read access to /proc/slabinfo (run as root)")
+                .style(Style::default().fg(Color::DarkGray)),
+            chunks[1],
+        );
+        return;
+    }
+
+    let rows = monitor.slab_caches.iter().map(|cache| {
+        let growth_style = match cache.growth {
+            growth if growth > 0 => Style::default().fg(Color::Yellow),
+            _ => Style::default().fg(Color::DarkGray),
+        };
+        Row::new(vec![
+            Cell::from(cache.name.clone()),
+            Cell::from(format!("{}/{}", cache.active_objs, cache.num_objs)),
+            Cell::from(format_bytes(cache.obj_size)),
+            Cell::from(format_bytes(cache.size)),
+            Cell::from(format_bytes_delta(cache.growth)).style(growth_style),
+        ])
+    });
+    let widths = [
+        Constraint::Length(28),
+        Constraint::Length(20),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ];
+    let header = Row::new(["Cache", "Objects", "Obj Size", "Size", "Growth"])
+        .style(Style::default().fg(Color::DarkGray));
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(Style::default().fg(Color::Yellow))
+        .highlight_symbol(">> ");
+    let mut state = TableState::default().with_selected(Some(monitor.view_selection));
+    f.render_stateful_widget(table, chunks[1], &mut state);
+}
+
+pub fn render_page_cache(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let hints = "Tab: next view | PgUp/PgDn: scroll | c: scan | g: files/directories";
+    let block = view_block(View::PageCache, hints);
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let entries = monitor.page_cache_entries();
+    let total: u64 = monitor
+        .page_cache_files
+        .iter()
+        .map(|entry| entry.cached)
+        .sum();
+    let grouping = match monitor.page_cache_grouping {
+        CacheGrouping::Files => "files",
+        CacheGrouping::Directories => "directories",
+    };
+    let summary = if monitor.page_cache_scan.is_some() {
+        format!("Scanning... {} files checked", monitor.page_cache_progress)
+    } else if monitor.config.page_cache_dirs.is_empty() {
+        String::from("Add directories to page_cache_dirs in the config to inspect the page cache")
+    } else if monitor.page_cache_files.is_empty() {
+        format!(
+            "Press c to scan {}",
+            monitor.config.page_cache_dirs.join(", ")
+        )
+    } else {
+        format!(
+            "{} cached in {} files, by {grouping}",
+            format_bytes(total),
+            monitor.page_cache_files.len()
+        )
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(inner);
+    f.render_widget(Paragraph::new(summary), chunks[0]);
+
+    let rows = entries.iter().map(|entry| {
+        let percentage = if entry.size > 0 {
+            entry.cached as f32 / entry.size as f32 * 100.0
+        } else {
+            0.0
+        };
+        Row::new(vec![
+            Cell::from(format_bytes(entry.cached)),
+            Cell::from(format_bytes(entry.size)),
+            Cell::from(format!("{percentage:.0}%")),
+            Cell::from(entry.path.display().to_string()),
+        ])
+    });
+    let widths = [
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Min(20),
+    ];
+    let header =
+        Row::new(["Cached", "Size", "%", "Path"]).style(Style::default().fg(Color::DarkGray));
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(Style::default().fg(Color::Yellow))
+        .highlight_symbol(">> ");
+    let mut state = TableState::default().with_selected(Some(monitor.view_selection));
+    f.render_stateful_widget(table, chunks[1], &mut state);
+}
+
+pub fn render_sysctls(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let hints =
+        "Tab: next view | PgUp/PgDn: select | +/-: edit | a: apply | r: revert | R: revert all";
+    let value = |value: Option<u64>| value.map_or_else(|| "-".to_string(), |v| v.to_string());
+
+    let rows = monitor.sysctls.iter().map(|setting| {
+        let pending_style = if setting.is_modified() {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let current_style = if setting.current != setting.original {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(setting.display_name()),
+            Cell::from(value(setting.current)).style(current_style),
+            Cell::from(value(setting.pending)).style(pending_style),
+            Cell::from(value(setting.original)),
+        ])
+    });
+    let widths = [
+        Constraint::Length(28),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+    ];
+    let header = Row::new(["Setting", "Current", "Pending", "Startup"])
+        .style(Style::default().fg(Color::DarkGray));
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(view_block(View::Sysctl, hints))
+        .row_highlight_style(Style::default().fg(Color::Yellow))
+        .highlight_symbol(">> ");
+    let mut state = TableState::default().with_selected(Some(monitor.view_selection));
+    f.render_stateful_widget(table, area, &mut state);
+}
+
+pub fn render_suspended(f: &mut Frame<'_>, area: Rect, monitor: &RamMonitor) {
+    let hints = "Tab: next view | PgUp/PgDn: select | u: resume";
+    let Some(suspend) = &monitor.config.suspend else {
+        let paragraph = Paragraph::new("Suspension is disabled, configure suspend to enable it")
+            .block(view_block(View::Suspended, hints));
+        f.render_widget(paragraph, area);
+        return;
+    };
+    if monitor.suspended.is_empty() {
+        let paragraph = Paragraph::new(format!(
+            "No processes suspended (suspends {} at {}%, resumes below {}%)",
+            suspend.processes.join(", "),
+            suspend.percent,
+            suspend.recovery_percent
+        ))
+        .block(view_block(View::Suspended, hints));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let max_secs = suspend.max_suspend_secs.unwrap_or(DEFAULT_MAX_SUSPEND_SECS);
+    let rows = monitor.suspended.iter().map(|process| {
+        let elapsed = process.since.elapsed();
+        let remaining = Duration::from_secs(max_secs).saturating_sub(elapsed);
+        Row::new(vec![
+            Cell::from(process.name.clone()),
+            Cell::from(process.pid.to_string()),
+            Cell::from(format_timestamp(elapsed)),
+            Cell::from(format_eta(remaining)),
+        ])
+    });
+    let widths = [
+        Constraint::Length(28),
+        Constraint::Length(10),
+        Constraint::Length(12),
+        Constraint::Length(14),
+    ];
+    let header = Row::new(["Process", "PID", "Suspended", "Auto resume"])
+        .style(Style::default().fg(Color::DarkGray));
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(view_block(View::Suspended, hints))
+        .row_highlight_style(Style::default().fg(Color::Yellow))
+        .highlight_symbol(">> ");
+    let mut state = TableState::default().with_selected(Some(monitor.view_selection));
+    f.render_stateful_widget(table, area, &mut state);
+}
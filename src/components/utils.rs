@@ -1,5 +1,5 @@
 use ratatui::style::Color;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::components::constants::{CRITICAL_THRESHOLD, WARNING_THRESHOLD};
 
@@ -19,6 +19,60 @@ pub fn format_timestamp(duration: Duration) -> String {
     }
 }
 
+// Format a forecast duration as an approximate countdown
+pub fn format_eta(duration: Duration) -> String {
+    // Seconds
+    if duration.as_secs() < 60 {
+        format!("~{}s", duration.as_secs())
+    }
+    // Minutes
+    else if duration.as_secs() < 3600 {
+        format!("~{} min", duration.as_secs() / 60)
+    }
+    // Hours
+    else {
+        format!("~{}h", duration.as_secs() / 3600)
+    }
+}
+
+// Format a wall-clock time as an ISO 8601 UTC timestamp, e.g. for the log file
+pub fn format_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let (days, secs_of_day) = (secs / 86400, secs % 86400);
+
+    // Convert days since the epoch to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
+
+// Check whether a periodic refresh is due
+pub fn is_due(last: Option<Instant>, interval_ms: u128) -> bool {
+    last.map(|time| time.elapsed().as_millis() >= interval_ms)
+        .unwrap_or(true)
+}
+
 // Get the color based on the percentage of used RAM
 pub fn get_usage_color(percentage: f32) -> Color {
     if percentage >= CRITICAL_THRESHOLD {
@@ -35,6 +89,24 @@ pub fn calculate_percentage(used: u64, total: u64) -> f32 {
     (used as f32 / total as f32) * 100.0
 }
 
+// Format a byte count with the largest fitting unit
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f32;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1}{}", UNITS[unit])
+}
+
+// Format a signed byte delta, e.g. "+12.0MB"
+pub fn format_bytes_delta(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{sign}{}", format_bytes(delta.unsigned_abs()))
+}
+
 // Convert bytes to gigabytes
 pub fn bytes_to_gb(bytes: u64) -> f32 {
     bytes as f32 / 1024.0 / 1024.0 / 1024.0
@@ -1,7 +1,9 @@
 mod components;
 
 use std::{
+    env,
     io::{self, stdout},
+    panic, process,
     time::{Duration, Instant},
 };
 
@@ -13,22 +15,70 @@ use crossterm::{
 
 use ratatui::{Terminal, prelude::CrosstermBackend};
 
-use components::{event_handler, structs::RamMonitor};
+use components::{cli, event_handler, paths::Paths, structs::RamMonitor, suspend};
+
+/// Restores the terminal and resumes stopped processes when dropped, so that an error
+/// returned from main leaves the system as it was found
+struct ExitGuard;
+
+impl Drop for ExitGuard {
+    fn drop(&mut self) {
+        restore_on_exit();
+    }
+}
+
+/// Resumes every process the observer stopped and restores the terminal
+/// Errors are ignored, as this runs while exiting
+fn restore_on_exit() {
+    suspend::resume_stopped();
+    let _ = disable_raw_mode();
+    let _ = stdout().execute(LeaveAlternateScreen);
+}
 
 /// RAM Monitor Application Entry Point
 /// Controls:
 /// - Up/Down: Navigate actions
 /// - Enter: Execute selected action
 /// - 1-5: Quick execute actions
+/// - 6-8: Quick execute kernel actions (Linux)
 /// - Shift+A: Cycle auto-action
 /// - Shift+T: Cycle threshold
+/// - Shift+P: Cycle profile
+/// - Shift+C: Toggle host / cgroup memory view
+/// - Tab/Shift+Tab: Switch bottom panel view
+/// - PageUp/PageDown: Navigate rows within the view
 /// - Q: Quit
+///
+/// Command-line commands such as --evict run without starting the TUI, see --help
 fn main() -> io::Result<()> {
+    let args = match cli::parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            process::exit(2);
+        }
+    };
+    let paths = Paths::resolve(args.config.as_deref());
+    if let Some(command) = args.command {
+        process::exit(cli::run(command, &paths));
+    }
+
+    // Release builds abort on panic without running Drop, so the hook restores too
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        restore_on_exit();
+        default_hook(info);
+    }));
+
+    #[cfg(unix)]
+    suspend::install_signal_handlers();
+
     // Initialize terminal
+    let _guard = ExitGuard;
     enable_raw_mode()?;
     stdout().execute(EnterAlternateScreen)?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
-    let mut ram_monitor = RamMonitor::new();
+    let mut ram_monitor = RamMonitor::new(paths);
 
     // Main event loop
     loop {
@@ -49,8 +99,7 @@ fn main() -> io::Result<()> {
         }
     }
 
-    // Cleanup and restore terminal
-    disable_raw_mode()?;
-    stdout().execute(LeaveAlternateScreen)?;
+    // Resume here to log it, the guard covers the other exit paths
+    ram_monitor.resume_all("observer exiting");
     Ok(())
 }